@@ -7,21 +7,31 @@ layout(location = 0) out vec2 out_frag_uv;
 layout(location = 1) out vec4 out_frag_color;
 
 layout(push_constant) uniform PushConstants {
+    vec4 color;
     vec2 camera_position;
     vec2 native_resolution;
-    vec2 entity_position;
+    vec2 entity_translation;
+    vec2 entity_x_axis;
+    vec2 entity_y_axis;
+    vec2 quad_position;
     vec2 image_size;
     vec2 norm_image_coordinate;
     vec2 norm_image_size;
-    vec4 color;
 }
 pc;
 
 void main() {
+    // ENTITY SPACE
+    vec2 local_position = pc.quad_position + in_position.xy * pc.image_size;
+
+    // WORLD SPACE
+    vec2 world_position = pc.entity_translation + local_position.x * pc.entity_x_axis +
+                          local_position.y * pc.entity_y_axis;
+
     // MODEL POSITION
     vec2 model_position = vec2(2.0 / pc.native_resolution.x, -2.0 / pc.native_resolution.y);
 
-    model_position *= in_position.xy * pc.image_size + pc.entity_position - pc.camera_position;
+    model_position *= world_position - pc.camera_position;
     gl_Position = vec4(model_position, in_position.z, 1.0);
 
     // OUT
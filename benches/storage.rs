@@ -0,0 +1,104 @@
+//! Compares dense and sparse ComponentLists. Run it with
+//! `cargo bench --bench storage`.
+#[macro_use]
+extern crate serde;
+
+// The game is a binary, so we pull the storage in directly.
+#[path = "../src"]
+#[allow(dead_code, unused_imports)]
+mod game {
+    pub mod entities;
+}
+
+use game::entities::{GenerationalIndexAllocator, GenerationalIndexArray, GenerationalIndexValue};
+use std::time::{Duration, Instant};
+
+const ENTITY_COUNT: usize = 100_000;
+const FILL_PERCENTS: [usize; 4] = [1, 10, 50, 100];
+const ITERATIONS: u32 = 100;
+
+struct BenchValue(u64);
+
+impl GenerationalIndexValue for BenchValue {
+    fn is_active(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    println!("{} Entities, best of {} runs per test.", ENTITY_COUNT, ITERATIONS);
+    println!(
+        "{:>6} {:>8} {:>12} {:>12} {:>12} {:>12}",
+        "fill", "storage", "iter", "slots", "get", "set/unset"
+    );
+
+    for fill_percent in FILL_PERCENTS.iter() {
+        for sparse in [false, true].iter() {
+            let timings = benchmark(*fill_percent, *sparse);
+            println!(
+                "{:>5}% {:>8} {:>12?} {:>12?} {:>12?} {:>12?}",
+                fill_percent,
+                if *sparse { "sparse" } else { "dense" },
+                timings[0],
+                timings[1],
+                timings[2],
+                timings[3]
+            );
+        }
+    }
+}
+
+fn benchmark(fill_percent: usize, sparse: bool) -> [Duration; 4] {
+    let mut allocator = GenerationalIndexAllocator::new();
+    let mut array = if sparse {
+        GenerationalIndexArray::sparse()
+    } else {
+        GenerationalIndexArray::new()
+    };
+
+    let entities: Vec<_> = (0..ENTITY_COUNT)
+        .map(|_| {
+            array.expand_list();
+            allocator.allocate()
+        })
+        .collect();
+
+    // Spread the values out evenly, like a Component most Entities don't have.
+    let step = 100 / fill_percent;
+    let filled: Vec<_> = entities.iter().step_by(step).collect();
+    for (i, entity) in filled.iter().enumerate() {
+        array.set(entity, BenchValue(i as u64));
+    }
+
+    let iter = best_of(|| array.iter().map(|value| value.0).sum::<u64>());
+    let slots = best_of(|| array.slots().flatten().map(|(_, value)| value.0).sum::<u64>());
+    let get = best_of(|| {
+        entities
+            .iter()
+            .filter_map(|entity| array.get(entity))
+            .map(|value| value.0)
+            .sum::<u64>()
+    });
+
+    // Take out and put back a few values, the way spawning and despawning does.
+    let set_unset = best_of(|| {
+        for entity in filled.iter().step_by(filled.len() / 10 + 1) {
+            array.unset(entity);
+            array.set(entity, BenchValue(0));
+        }
+        0
+    });
+
+    [iter, slots, get, set_unset]
+}
+
+fn best_of(mut f: impl FnMut() -> u64) -> Duration {
+    (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
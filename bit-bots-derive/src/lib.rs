@@ -1,8 +1,12 @@
 extern crate proc_macro;
 use crate::proc_macro::TokenStream;
-use quote::quote;
-use syn::DeriveInput;
 use heck::SnekCase;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    DeriveInput, Ident, Token, Type,
+};
 
 #[proc_macro_derive(SerializableComponent)]
 pub fn serializable_component_derive(input: TokenStream) -> TokenStream {
@@ -21,3 +25,308 @@ pub fn serializable_component_derive(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// A single line in the Component Registry. It looks like this:
+///
+/// `list_name: ComponentType => serialized_name [FLAG, option]`
+///
+/// - `=> serialized_name` is optional. Components without it are not written into a
+///   `SerializedEntity` at all.
+/// - `[FLAG]` is optional. It is the `NonInspectableEntities` flag which gates this component
+///   in the `foreach` functions. Components without a flag are always visited.
+/// - `keep_on_dedup` can follow the flag, and means `foreach_component_dedup` will skip it.
+/// - `sparse` can follow the flag too, and stores the ComponentList as a sparse set. Use it for
+///   Components only a few Entities have.
+struct RegistryEntry {
+    list_name: Ident,
+    component: Type,
+    serialized_name: Option<Ident>,
+    flag: Option<Ident>,
+    keep_on_dedup: bool,
+    sparse: bool,
+}
+
+impl Parse for RegistryEntry {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let list_name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let component: Type = input.parse()?;
+
+        let serialized_name = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let mut flag = None;
+        let mut keep_on_dedup = false;
+        let mut sparse = false;
+        if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            let options: Punctuated<Ident, Token![,]> = content.parse_terminated(Ident::parse)?;
+
+            for option in options {
+                if option == "keep_on_dedup" {
+                    keep_on_dedup = true;
+                } else if option == "sparse" {
+                    sparse = true;
+                } else if flag.is_none() {
+                    flag = Some(option);
+                } else {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "A Component can only have one NonInspectableEntities flag.",
+                    ));
+                }
+            }
+        }
+
+        Ok(RegistryEntry {
+            list_name,
+            component,
+            serialized_name,
+            flag,
+            keep_on_dedup,
+            sparse,
+        })
+    }
+}
+
+struct Registry {
+    entries: Vec<RegistryEntry>,
+}
+
+impl Parse for Registry {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let entries: Punctuated<RegistryEntry, Token![,]> = input.parse_terminated(RegistryEntry::parse)?;
+
+        Ok(Registry {
+            entries: entries.into_iter().collect(),
+        })
+    }
+}
+
+/// Wraps a visit in a `NonInspectableEntities` check, if the entry has a flag.
+fn gate_on_flag(
+    flag: &Option<Ident>,
+    bitmask: &proc_macro2::TokenStream,
+    visit: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match flag {
+        Some(flag) => quote! {
+            if #bitmask.contains(crate::NonInspectableEntities::#flag) {
+                #visit
+            }
+        },
+        None => visit,
+    }
+}
+
+/// Generates `ComponentDatabase`, its `Default`, `foreach_component_list`,
+/// `foreach_component_list_mut`, `load_serialized_components` and a `ComponentListOf`
+/// impl for every Component from the Component Registry. Invoke it through `component_registry!`.
+#[proc_macro]
+pub fn impl_component_database(input: TokenStream) -> TokenStream {
+    let Registry { entries } = syn::parse_macro_input!(input as Registry);
+
+    let list_names: Vec<&Ident> = entries.iter().map(|e| &e.list_name).collect();
+    let components: Vec<&Type> = entries.iter().map(|e| &e.component).collect();
+
+    let new_lists = entries.iter().map(|e| {
+        let list_name = &e.list_name;
+        if e.sparse {
+            quote!(#list_name: crate::ComponentList::sparse(),)
+        } else {
+            quote!(#list_name: Default::default(),)
+        }
+    });
+
+    let bitmask = quote!(non_inspectable_entities);
+    let visits_mut = entries.iter().map(|e| {
+        let list_name = &e.list_name;
+        gate_on_flag(&e.flag, &bitmask, quote!(f(&mut self.#list_name);))
+    });
+    let visits = entries.iter().map(|e| {
+        let list_name = &e.list_name;
+        gate_on_flag(&e.flag, &bitmask, quote!(f(&self.#list_name);))
+    });
+
+    let transfers = entries.iter().filter_map(|e| {
+        let list_name = &e.list_name;
+        e.serialized_name.as_ref().map(|serialized_name| {
+            quote! {
+                if let Some(serialized_component) = serialized_entity.#serialized_name {
+                    self.#list_name.set_component_with_active(
+                        entity,
+                        serialized_component.inner,
+                        serialized_component.active,
+                    );
+                }
+            }
+        })
+    });
+
+    let expanded = quote! {
+        pub struct ComponentDatabase {
+            #(pub #list_names: crate::ComponentList<#components>,)*
+            size: usize,
+        }
+
+        impl ComponentDatabase {
+            /// This loops over every component list allowed by `non_inspectable_entities`.
+            /// Components without a flag in the Component Registry are always visited.
+            pub fn foreach_component_list_mut(
+                &mut self,
+                non_inspectable_entities: crate::NonInspectableEntities,
+                mut f: impl FnMut(&mut dyn crate::ComponentListBounds),
+            ) {
+                #(#visits_mut)*
+            }
+
+            /// This loops over every component list allowed by `non_inspectable_entities`.
+            /// Components without a flag in the Component Registry are always visited.
+            pub fn foreach_component_list(
+                &self,
+                non_inspectable_entities: crate::NonInspectableEntities,
+                mut f: impl FnMut(&dyn crate::ComponentListBounds),
+            ) {
+                #(#visits)*
+            }
+
+            /// An exact copy of every ComponentList, for WorldSnapshots.
+            pub fn snapshot(&self) -> ComponentDatabase {
+                ComponentDatabase {
+                    #(#list_names: self.#list_names.snapshot(),)*
+                    size: self.size,
+                }
+            }
+
+            /// Moves every serialized component out of the SerializedEntity and into its
+            /// ComponentList. Returns the singleton Marker, which is handled by the caller.
+            fn load_serialized_components(
+                &mut self,
+                entity: &crate::Entity,
+                serialized_entity: crate::SerializedEntity,
+            ) -> Option<crate::Marker> {
+                #(#transfers)*
+
+                serialized_entity.marker
+            }
+        }
+
+        impl Default for ComponentDatabase {
+            fn default() -> ComponentDatabase {
+                ComponentDatabase {
+                    #(#new_lists)*
+                    size: 0,
+                }
+            }
+        }
+
+        #(
+            impl crate::ComponentListOf<#components> for ComponentDatabase {
+                fn component_list(&self) -> &crate::ComponentList<#components> {
+                    &self.#list_names
+                }
+
+                fn component_list_mut(&mut self) -> &mut crate::ComponentList<#components> {
+                    &mut self.#list_names
+                }
+            }
+        )*
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Generates `SerializedEntity`, `foreach_component`, `foreach_component_mut` and
+/// `foreach_component_dedup` from the Component Registry. Invoke it through `component_registry!`.
+#[proc_macro]
+pub fn impl_serialized_entity(input: TokenStream) -> TokenStream {
+    let Registry { entries } = syn::parse_macro_input!(input as Registry);
+    let serialized: Vec<&RegistryEntry> = entries.iter().filter(|e| e.serialized_name.is_some()).collect();
+
+    let serialized_names: Vec<&Ident> = serialized
+        .iter()
+        .map(|e| e.serialized_name.as_ref().unwrap())
+        .collect();
+    let components: Vec<&Type> = serialized.iter().map(|e| &e.component).collect();
+
+    let bitmask = quote!(entity_bitmask);
+    let visits = serialized.iter().map(|e| {
+        let serialized_name = e.serialized_name.as_ref().unwrap();
+        gate_on_flag(
+            &e.flag,
+            &bitmask,
+            quote! {
+                if let Some(serialized_component) = &self.#serialized_name {
+                    f(&serialized_component.inner, &serialized_component.active);
+                }
+            },
+        )
+    });
+
+    let visits_mut = serialized.iter().map(|e| {
+        let serialized_name = e.serialized_name.as_ref().unwrap();
+        quote! {
+            if let Some(serialized_component) = &mut self.#serialized_name {
+                f(&mut serialized_component.inner);
+            }
+        }
+    });
+
+    let dedups = serialized.iter().filter(|e| !e.keep_on_dedup).map(|e| {
+        let serialized_name = e.serialized_name.as_ref().unwrap();
+        quote! {
+            if let Some(serialized_component) = &self.#serialized_name {
+                if f(&serialized_component.inner, &serialized_component.active) {
+                    self.#serialized_name = None;
+                }
+            }
+        }
+    });
+
+    let expanded = quote! {
+        #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+        #[serde(default)]
+        pub struct SerializedEntity {
+            #(pub #serialized_names: crate::SerializedComponentWrapper<#components>,)*
+
+            pub id: uuid::Uuid,
+            pub marker: Option<crate::Marker>,
+            /// For a Prefab inheritor, the paths of the fields it overrides, like
+            /// `sprite.inner.sprite_name`. A Component with no paths here is overridden whole.
+            pub prefab_overrides: Vec<String>,
+        }
+
+        impl SerializedEntity {
+            pub fn foreach_component(
+                &mut self,
+                entity_bitmask: crate::NonInspectableEntities,
+                mut f: impl FnMut(&dyn crate::ComponentBounds, &bool),
+                f_util: Option<impl FnMut(&mut uuid::Uuid, &mut Option<crate::Marker>)>,
+            ) {
+                #(#visits)*
+
+                if let Some(mut f_util) = f_util {
+                    f_util(&mut self.id, &mut self.marker);
+                }
+            }
+
+            pub fn foreach_component_mut(&mut self, mut f: impl FnMut(&mut dyn crate::ComponentBounds)) {
+                #(#visits_mut)*
+            }
+
+            pub fn foreach_component_dedup(
+                &mut self,
+                mut f: impl FnMut(&dyn crate::ComponentBounds, &bool) -> bool,
+            ) {
+                #(#dedups)*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -6,6 +6,7 @@ use log4rs::{
 };
 use std::fs;
 
+mod cooked_layout;
 mod imgui_initialization;
 mod serialization_util;
 mod shader_builder;
@@ -18,6 +19,7 @@ fn main() -> Result<(), Error> {
     imgui_initialization::initialize_imgui()?;
     shader_builder::build()?;
     sprite_packer::pack_sprites()?;
+    cooked_layout::hash_layout()?;
 
     Ok(())
 }
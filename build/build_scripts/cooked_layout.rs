@@ -0,0 +1,33 @@
+use super::Error;
+use glob::glob;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+/// Hashes every source file with a serialized type in it, along with our dependencies,
+/// so the game can tell when a cooked file was written with a different layout.
+/// Bincode isn't self-describing, so it can't tell on its own.
+pub fn hash_layout() -> Result<(), Error> {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let mut paths = vec![];
+    for entry in glob("src/**/*.rs")? {
+        paths.push(entry?);
+    }
+    paths.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for path in paths {
+        let text = fs::read_to_string(&path)?;
+        if text.contains("Deserialize") {
+            path.hash(&mut hasher);
+            text.hash(&mut hasher);
+        }
+    }
+    fs::read_to_string("Cargo.lock")?.hash(&mut hasher);
+
+    println!("cargo:rustc-env=COOKED_LAYOUT_HASH={:016x}", hasher.finish());
+
+    Ok(())
+}
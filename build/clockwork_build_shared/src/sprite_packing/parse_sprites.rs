@@ -6,9 +6,36 @@ use serde_json::Value;
 use sheep::{Format, InputSprite, MaxrectsPacker, SpriteAnchor};
 use std::fs;
 
+pub const PNG_GLOB: &str = "assets/textures/**/*.png";
 const JSON_GLOB: &str = "assets/textures/**/";
 const PNG_FNAME: &str = "assets/gen/textures/packed_sheet_";
 
+/// Loads every PNG in the textures directory and packs them all. The game calls this too,
+/// in debug builds, when one of them changes.
+pub fn pack_all_sprites() -> Result<(), Error> {
+    let mut file_names = Vec::new();
+    let mut input_sprites = Vec::new();
+
+    for entry in glob(PNG_GLOB)? {
+        let entry = entry?;
+        let file_name = entry.file_stem().unwrap().to_str().unwrap().to_owned();
+
+        let image = image::open(&entry)?;
+        let image = match image.as_rgba8() {
+            Some(image) => image,
+            None => bail!("{} isn't an RGBA image!", entry.display()),
+        };
+
+        let dimensions = (image.width(), image.height());
+        let bytes = image.pixels().flat_map(|it| it.0.iter().map(|it| *it)).collect();
+
+        file_names.push(file_name);
+        input_sprites.push(InputSprite { bytes, dimensions });
+    }
+
+    pack_sprites(input_sprites, file_names)
+}
+
 pub fn pack_sprites(input_sprites: Vec<InputSprite>, file_names: Vec<String>) -> Result<(), Error> {
     let results = sheep::pack::<MaxrectsPacker>(input_sprites, 4, sheep::MaxrectsOptions::default());
 
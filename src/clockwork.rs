@@ -1,17 +1,24 @@
 use super::{
-    systems::grid_system::Grid, systems::*, Ecs, HardwareInterface, ImGui, ImGuiDrawCommands,
-    ResourcesDatabase, TimeKeeper,
+    scene_graph, stale_handles,
+    systems::hot_reload_system::{self, HotReload},
+    systems::scene_system::SceneTransition,
+    systems::scheduler::Scheduler,
+    systems::*,
+    Ecs, HardwareInterface, ImGui, ImGuiDrawCommands, ResourcesDatabase, Scene, SceneLayer, SceneStack,
+    TimeKeeper,
 };
 use anyhow::Error;
 use array2d::Array2D;
 
 pub struct Clockwork {
-    pub ecs: Ecs,
+    pub scene_stack: SceneStack,
     pub action_map: ActionMap,
-    pub grid: grid_system::Grid,
     pub hardware_interfaces: HardwareInterface,
     pub resources: ResourcesDatabase,
+    pub scheduler: Scheduler,
     pub time_keeper: TimeKeeper,
+    /// Only in debug builds.
+    hot_reload: Option<HotReload>,
 }
 
 impl Clockwork {
@@ -21,29 +28,45 @@ impl Clockwork {
         let mut hardware_interfaces = HardwareInterface::new(&resources.config)?;
         resources.initialize(&mut hardware_interfaces.renderer)?;
 
-        let (ecs, grid) = Clockwork::start_scene(&mut resources, &mut hardware_interfaces)?;
+        let base_layer = Clockwork::start_scene(
+            Scene::new("1".to_string()),
+            &mut resources,
+            &mut hardware_interfaces,
+        )?;
+
+        let mut scheduler = Scheduler::new();
+        Ecs::register_systems(&mut scheduler);
+
+        let hot_reload = if cfg!(debug_assertions) {
+            HotReload::new()
+                .map_err(|e| error!("We couldn't watch for files changing on disk. {}", e))
+                .ok()
+        } else {
+            None
+        };
 
         Ok(Clockwork {
-            ecs,
+            scene_stack: SceneStack::new(base_layer),
             hardware_interfaces,
             resources,
+            scheduler,
             action_map: ActionMap::default(),
             time_keeper: TimeKeeper::new(),
-            grid,
+            hot_reload,
         })
     }
 
     pub fn main_loop(&mut self) -> Result<(), Error> {
         // TICK STRUCTS
         let mut imgui = ImGui::new(
-            &self.ecs.entity_allocator,
+            &self.scene_stack.top().ecs.entity_allocator,
             &self.hardware_interfaces.window,
             &self.resources.config,
         );
         renderer_system::initialize_imgui(&mut self.hardware_interfaces.renderer, &mut imgui)?;
 
         loop {
-            let scene_mode = scene_system::current_scene_mode();
+            let scene_mode = self.scene_stack.top().ecs.scene_state.current_scene_mode();
             self.time_keeper.start_frame();
 
             // GET INPUT PER FRAME
@@ -67,14 +90,22 @@ impl Clockwork {
                 self.time_keeper.delta_time,
             )?;
 
+            let is_overlay = self.scene_stack.overlay_count() > 0;
             imgui_system::imgui_main(
-                &mut self.ecs,
+                &mut self.scene_stack.top_mut().ecs,
+                is_overlay,
                 &mut self.resources,
                 &mut self.hardware_interfaces,
                 &mut ui_handler,
                 &self.time_keeper,
+                &self.scheduler,
             );
 
+            // The editor has seen what moved, and it's the last one to look.
+            for layer in self.scene_stack.layers_mut() {
+                scene_graph::clear_transform_changes(&mut layer.ecs.component_database.transforms);
+            }
+
             if scene_mode == SceneMode::Draft {
                 // tilemap_system::update_tilemaps_and_tilesets(
                 //     &mut self.ecs.component_database.tilemaps,
@@ -89,22 +120,57 @@ impl Clockwork {
             // Make the Action Map:
             self.action_map.update(&self.hardware_interfaces.input.kb_input);
 
-            // Update
+            // Update. Only the top Scene gets input.
+            let covered_action_map = ActionMap::default();
             while self.time_keeper.accumulator >= self.time_keeper.delta_time {
-                if scene_mode == SceneMode::Playing {
-                    self.ecs.update(&mut self.grid, &self.action_map)?;
-                    self.ecs
-                        .update_resources(&self.resources, self.time_keeper.delta_time);
+                for (layer, is_top) in self.scene_stack.updating_layers_mut() {
+                    let layer_mode = layer.ecs.scene_state.current_scene_mode();
+                    let action_map = if is_top {
+                        &self.action_map
+                    } else {
+                        &covered_action_map
+                    };
+
+                    let mut system_context =
+                        layer.system_context(action_map, &self.resources, self.time_keeper.delta_time);
+                    self.scheduler.run_fixed_update(layer_mode, &mut system_context);
                 }
                 self.time_keeper.accumulator -= self.time_keeper.delta_time;
             }
 
+            for layer in self.scene_stack.layers_mut() {
+                let layer_mode = layer.ecs.scene_state.current_scene_mode();
+                let mut system_context =
+                    layer.system_context(&self.action_map, &self.resources, self.time_keeper.delta_time);
+                self.scheduler.run_pre_render(layer_mode, &mut system_context);
+            }
+
             // RENDER
             self.pre_render()?;
             self.render(ui_handler)?;
 
+            // CHANGE SCENE MODE?
+            self.check_scene_mode_change(&mut imgui);
+
+            // LOOK FOR STALE HANDLES?
+            self.check_handle_validation(&mut imgui);
+
             // CHANGE SCENE?
             self.check_scene_change(&mut imgui)?;
+
+            // RELOAD FILES CHANGED ON DISK?
+            if let Some(hot_reload) = &mut self.hot_reload {
+                hot_reload_system::reload_changed_files(
+                    hot_reload,
+                    &mut self.scene_stack,
+                    &mut self.resources,
+                );
+                hot_reload_system::reload_changed_textures(
+                    hot_reload,
+                    &mut self.resources,
+                    &mut self.hardware_interfaces.renderer,
+                );
+            }
         }
 
         imgui.save_meta_data()?;
@@ -113,25 +179,23 @@ impl Clockwork {
     }
 
     pub fn pre_render(&mut self) -> Result<(), Error> {
-        renderer_system::pre_draw(
-            &mut self.ecs.component_database,
-            &mut self.resources,
-            &mut self.hardware_interfaces.renderer,
-        )?;
+        for layer in self.scene_stack.layers_mut() {
+            renderer_system::pre_draw(
+                &mut layer.ecs.component_database,
+                &mut self.resources,
+                &mut self.hardware_interfaces.renderer,
+            )?;
+        }
 
         Ok(())
     }
 
     pub fn render(&mut self, ui_handler: UiHandler<'_>) -> Result<(), Error> {
-        // Update transform by walking the scene graph...
-        scene_graph::walk_graph(
-            &mut self.ecs.component_database.transforms,
-            &self.ecs.component_database.graph_nodes,
-        );
-
         let mut draw_commands = DrawCommand::default();
 
-        self.ecs.render(&mut draw_commands, &self.resources);
+        for layer in self.scene_stack.layers_mut() {
+            layer.ecs.render(&mut draw_commands, &self.resources);
+        }
         draw_commands.imgui = Some(ImGuiDrawCommands {
             draw_data: ui_handler.ui.render(),
             imgui_dimensions: ui_handler
@@ -156,50 +220,106 @@ impl Clockwork {
         Ok(())
     }
 
-    fn check_scene_change(&mut self, imgui: &mut ImGui) -> Result<(), Error> {
-        let should_change_scene = {
-            let next_scene = scene_system::NEXT_SCENE.lock().unwrap();
-            next_scene.is_some()
-        };
+    /// Only the top Scene has the editor, so it's the only one which can change SceneMode.
+    fn check_scene_mode_change(&mut self, imgui: &mut ImGui) {
+        let top = self.scene_stack.top_mut();
 
-        if should_change_scene {
-            let (ecs, grid) = Clockwork::start_scene(&mut self.resources, &mut self.hardware_interfaces)?;
-            self.ecs = ecs;
-            self.grid = grid;
+        if let Some(scene_mode) = top.ecs.scene_state.take_scene_mode_request() {
+            top.set_scene_mode(scene_mode, &mut imgui.meta_data.stored_ids);
 
             // Clear up the ImGui
             imgui.meta_data.entity_list_information.clear();
             imgui.meta_data.entity_vec.clear();
-            imgui.meta_data.stored_ids.clear();
         }
 
+        // A Step is one tick of Playing systems. It's always a 60th of a second,
+        // no matter how long this frame took.
+        if top.ecs.scene_state.take_step_request()
+            && top.ecs.scene_state.current_scene_mode() == SceneMode::Paused
+        {
+            let mut system_context =
+                top.system_context(&self.action_map, &self.resources, TimeKeeper::SIXTIETH);
+            self.scheduler
+                .run_fixed_update(SceneMode::Playing, &mut system_context);
+        }
+    }
+
+    fn check_handle_validation(&mut self, imgui: &mut ImGui) {
+        let top = self.scene_stack.top_mut();
+
+        if let Some(clear) = top.ecs.scene_state.take_handle_validation_request() {
+            let stale_handles = stale_handles::validate_entity_handles(&mut top.ecs, &mut top.grid, clear);
+            stale_handles::validate_selection(&mut imgui.meta_data.stored_ids, &top.ecs.entity_allocator);
+
+            if stale_handles.is_empty() {
+                info!("No stale Entity handles in {}.", top.ecs.scene_state.current());
+            }
+        }
+    }
+
+    fn check_scene_change(&mut self, imgui: &mut ImGui) -> Result<(), Error> {
+        let (index, transition) = match self.scene_stack.take_transition() {
+            Some(transition) => transition,
+            None => return Ok(()),
+        };
+
+        match transition {
+            SceneTransition::Switch(scene) => {
+                let base_layer =
+                    Clockwork::start_scene(scene, &mut self.resources, &mut self.hardware_interfaces)?;
+                self.scene_stack.replace(base_layer);
+
+                imgui.meta_data.stored_ids.clear();
+            }
+
+            SceneTransition::PushOverlay { scene, update_below } => {
+                let overlay =
+                    Clockwork::start_scene(scene, &mut self.resources, &mut self.hardware_interfaces)?;
+                let selection = std::mem::take(&mut imgui.meta_data.stored_ids);
+                self.scene_stack.push(overlay, update_below, selection);
+            }
+
+            SceneTransition::Pop => match self.scene_stack.pop(index) {
+                Some(selection) => {
+                    info!(
+                        "Returning to {}",
+                        self.scene_stack.top().ecs.scene_state.current()
+                    );
+                    imgui.meta_data.stored_ids = selection;
+                }
+                None => {
+                    error!("Can't pop the base Scene! Switch Scenes instead.");
+                    return Ok(());
+                }
+            },
+        }
+
+        // Clear up the ImGui
+        imgui.meta_data.entity_list_information.clear();
+        imgui.meta_data.entity_vec.clear();
+
         Ok(())
     }
 
     fn start_scene(
+        scene: Scene,
         resources: &mut ResourcesDatabase,
         hardware_interfaces: &mut HardwareInterface,
-    ) -> Result<(Ecs, Grid), Error> {
-        // Change the Scene Name!
-        {
-            let next_scene = scene_system::NEXT_SCENE.lock().unwrap().take();
-            let mut value = scene_system::CURRENT_SCENE.lock().unwrap();
-            if let Some(next_scene) = next_scene {
-                info!("Loading {}", next_scene);
-                *value = next_scene;
-            }
-        }
+    ) -> Result<SceneLayer, Error> {
+        info!("Loading {}", scene);
 
         // Grid
         let mut grid = Array2D::filled_with(None, 5, 10);
-        scene_graph::clear_root();
 
         // Initialize the ECS
-        let mut ecs = Ecs::new(&resources.prefabs())?;
+        let mut ecs = Ecs::new(scene, &resources.prefabs())?;
         ecs.game_start(resources, hardware_interfaces, &mut grid)?;
 
+        // Broken references in the Scene's files get reported, but we leave them be.
+        stale_handles::validate_entity_handles(&mut ecs, &mut grid, false);
+
         info!("..Scene Loaded!");
 
-        Ok((ecs, grid))
+        Ok(SceneLayer::new(ecs, grid))
     }
 }
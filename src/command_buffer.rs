@@ -0,0 +1,187 @@
+use super::{
+    grid_system::{self, Grid},
+    prefab_system, scene_graph, ComponentBounds, ComponentDatabase, ComponentListOf, Ecs, Entity, GraphNode,
+    Name, PrefabMap, SceneMode, SerializationMarker, Transform,
+};
+use std::any::TypeId;
+use uuid::Uuid;
+
+type ComponentEdit = Box<dyn FnOnce(&mut ComponentDatabase)>;
+
+enum Command {
+    SpawnPrefab(Uuid),
+    Despawn(Entity),
+    Component {
+        entity: Entity,
+        component_type: TypeId,
+        edit: ComponentEdit,
+    },
+}
+
+/// Systems can't create or remove entities (or components) while they're iterating
+/// over ComponentLists, so they push Commands in here instead. The Scheduler applies
+/// them with `apply_commands` at the end of every Stage.
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+impl CommandBuffer {
+    /// Spawns the Prefab at the Root of the Scene Graph. In Draft, it's part of the Scene.
+    pub fn spawn_prefab(&mut self, prefab_id: Uuid) {
+        self.commands.push(Command::SpawnPrefab(prefab_id));
+    }
+
+    /// Despawns the Entity and all of its children in the Scene Graph.
+    pub fn despawn(&mut self, entity: Entity) {
+        self.commands.push(Command::Despawn(entity));
+    }
+
+    /// Adds the Component to the Entity, replacing whatever was there.
+    pub fn add_component<T>(&mut self, entity: Entity, component: T)
+    where
+        T: ComponentBounds + Clone + Default + typename::TypeName + 'static,
+        ComponentDatabase: ComponentListOf<T>,
+    {
+        self.commands.push(Command::Component {
+            entity,
+            component_type: TypeId::of::<T>(),
+            edit: Box::new(move |component_database: &mut ComponentDatabase| {
+                ComponentListOf::<T>::component_list_mut(component_database)
+                    .set_component(&entity, component);
+            }),
+        });
+    }
+
+    pub fn remove_component<T>(&mut self, entity: Entity)
+    where
+        T: ComponentBounds + Clone + 'static,
+        ComponentDatabase: ComponentListOf<T>,
+    {
+        self.commands.push(Command::Component {
+            entity,
+            component_type: TypeId::of::<T>(),
+            edit: Box::new(move |component_database: &mut ComponentDatabase| {
+                ComponentListOf::<T>::component_list_mut(component_database).unset(&entity);
+            }),
+        });
+    }
+}
+
+/// This is the sync point for the CommandBuffer. Commands are applied in the order
+/// they were pushed.
+pub fn apply_commands(ecs: &mut Ecs, grid: &mut Grid, prefabs: &PrefabMap) {
+    let commands = std::mem::take(&mut ecs.commands.commands);
+
+    for command in commands {
+        match command {
+            Command::SpawnPrefab(prefab_id) => spawn_prefab(ecs, grid, prefab_id, prefabs),
+            Command::Despawn(entity) => despawn(ecs, grid, &entity),
+            Command::Component {
+                entity,
+                component_type,
+                edit,
+            } => {
+                if ecs.entities.contains(&entity) == false {
+                    error!(
+                        "Tried to edit a component on {}, but it was already despawned.",
+                        entity
+                    );
+                    continue;
+                }
+
+                unlink_component(ecs, grid, &entity, component_type);
+                edit(&mut ecs.component_database);
+                link_component(ecs, grid, &entity, component_type);
+            }
+        }
+    }
+}
+
+fn spawn_prefab(ecs: &mut Ecs, grid: &mut Grid, prefab_id: Uuid, prefabs: &PrefabMap) {
+    let entity = prefab_system::instantiate_entity_from_prefab(ecs, prefab_id, prefabs);
+
+    // If the Prefab failed to load, the Entity has already been cleaned up.
+    if ecs.entities.contains(&entity) {
+        if ecs.scene_state.current_scene_mode() == SceneMode::Draft {
+            ecs.component_database
+                .serialization_markers
+                .set_component(&entity, SerializationMarker::new());
+        }
+
+        for member in scene_graph::collect_descendants(&entity, &ecs.component_database.graph_nodes) {
+            if let Some(transform_c) = ecs.component_database.transforms.get(&member) {
+                grid_system::register_entity(grid, member, transform_c.inner().world_position(), None);
+            }
+        }
+    }
+}
+
+fn despawn(ecs: &mut Ecs, grid: &mut Grid, entity: &Entity) {
+    if ecs.entities.contains(entity) == false {
+        error!("Tried to despawn {}, but it was already despawned.", entity);
+        return;
+    }
+
+    // Children go first, so their parents are still around to forget them.
+    let descendants = scene_graph::collect_descendants(entity, &ecs.component_database.graph_nodes);
+    for descendant in descendants.iter().rev() {
+        if let Some(transform_c) = ecs.component_database.transforms.get_mut(descendant) {
+            scene_graph::remove_from_scene_graph(transform_c);
+        }
+        grid_system::unregister_entity(grid, descendant);
+        ecs.singleton_database
+            .associated_entities
+            .retain(|_, associated| *associated != *descendant);
+
+        if ecs.remove_entity(descendant) == false {
+            error!(
+                "Couldn't despawn {}. It may be despawned twice in the Scene Graph.",
+                Name::get_name_quick(&ecs.component_database.names, descendant)
+            );
+        }
+    }
+}
+
+/// Before a Transform or GraphNode is replaced or removed, we take it out of the
+/// Scene Graph and the Grid so nothing points at the old one.
+fn unlink_component(ecs: &mut Ecs, grid: &mut Grid, entity: &Entity, component_type: TypeId) {
+    let component_database = &mut ecs.component_database;
+    let scene_graph = &mut ecs.scene_graph;
+
+    if component_type == TypeId::of::<Transform>() {
+        if let Some(transform_c) = component_database.transforms.get_mut(entity) {
+            scene_graph::remove_from_scene_graph(transform_c);
+        }
+        grid_system::unregister_entity(grid, entity);
+    } else if component_type == TypeId::of::<GraphNode>() {
+        // Our children move up to the Root.
+        let children: Vec<Entity> = component_database
+            .graph_nodes
+            .get(entity)
+            .and_then(|node| node.inner().children.as_ref())
+            .map(|children| children.iter().filter_map(|child| child.target).collect())
+            .unwrap_or_default();
+
+        for child in children {
+            if let Some(transform_c) = component_database.transforms.get_mut(&child) {
+                scene_graph::remove_from_scene_graph(transform_c);
+                scene_graph.add_to_scene_graph(transform_c, &component_database.serialization_markers);
+            }
+        }
+    }
+}
+
+/// After a Transform is added, it goes into the Scene Graph (at the Root) and into the Grid.
+fn link_component(ecs: &mut Ecs, grid: &mut Grid, entity: &Entity, component_type: TypeId) {
+    let component_database = &mut ecs.component_database;
+    let scene_graph = &mut ecs.scene_graph;
+
+    if component_type == TypeId::of::<Transform>() {
+        if let Some(transform_c) = component_database.transforms.get_mut(entity) {
+            let position = transform_c.inner().world_position();
+            scene_graph.add_to_scene_graph(transform_c, &component_database.serialization_markers);
+            grid_system::register_entity(grid, *entity, position, None);
+        }
+    }
+}
@@ -1,5 +1,40 @@
 pub use super::*;
 
+/// This is the Component Registry -- the single list of every Component in the game.
+/// It hands that list to `$generator`, which is one of the `bit-bots-derive` macros
+/// (`impl_component_database` or `impl_serialized_entity`).
+///
+/// To add a new Component, add a line here. The order here is the order we iterate
+/// (and inspect) components in, and the order they're written out in, so keep new
+/// Components at the end of the serialized ones to keep old files' diffs small. See
+/// `bit-bots-derive` for the line syntax.
+///
+/// GraphNodes can't be `sparse`, since Transforms point right at them and sparse
+/// storage moves its values around.
+macro_rules! component_registry {
+    ($generator:ident) => {
+        $generator! {
+            names: Name => name [NAME],
+            players: Player => player [sparse],
+            transforms: Transform => transform,
+            grid_objects: GridObject => grid_object,
+            scene_switchers: SceneSwitcher => scene_switcher [sparse],
+            graph_nodes: GraphNode => graph_node [GRAPH_NODE],
+            velocities: Velocity => velocity,
+            sprites: Sprite => sprite,
+            sound_sources: SoundSource => sound_source,
+            draw_rectangles: DrawRectangle => draw_rectangle,
+            bounding_boxes: physics_components::BoundingBox => bounding_box,
+            text_sources: TextSource => text_source,
+            follows: Follow => follow,
+            conversant_npcs: ConversantNPC => conversant_npc [sparse],
+            prefab_markers: PrefabMarker => prefab_marker [PREFAB, keep_on_dedup],
+            tags: Tags => tags,
+            serialization_markers: SerializationMarker [SERIALIZATION],
+        }
+    };
+}
+
 mod component;
 // pub mod component_serialization;
 mod component_utils;
@@ -16,6 +51,7 @@ mod scene_switcher;
 mod serialization_marker;
 mod sound_source;
 mod sprite;
+mod tags;
 mod text_source;
 // pub mod tilemap;
 mod transform;
@@ -30,7 +66,7 @@ pub use {
         draw_layer::*,
         imgui_component_utils, Approach, DrawCommand, EditingMode,
         GameWorldDrawCommands, ImGuiDrawCommands, PositionalRect, SerializableEntityReference,
-        SerializablePrefabReference, Tile, TransformParent,
+        SerializablePrefabReference, SpriteRunningData, Tile, TransformParent,
     },
     conversant_npc::*,
     draw_rectangle::*,
@@ -44,6 +80,7 @@ pub use {
     serialization_marker::SerializationMarker,
     sound_source::SoundSource,
     sprite::Sprite,
+    tags::Tags,
     text_source::TextSource,
     transform::Transform,
     velocity::Velocity,
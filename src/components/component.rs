@@ -1,4 +1,6 @@
-use super::{ComponentBounds, Entity, GenerationalIndexValue};
+use super::{ComponentBounds, Entity, GenerationalIndexArray, GenerationalIndexValue};
+
+pub type ComponentList<T> = GenerationalIndexArray<Component<T>>;
 
 #[derive(Debug)]
 pub struct Component<T: ComponentBounds + Clone> {
@@ -40,6 +42,11 @@ impl<T: ComponentBounds + Clone> Component<T> {
         self.inner.clone()
     }
 
+    /// An exact copy, for WorldSnapshots.
+    pub fn snapshot(&self) -> Self {
+        Self::with_active(&self.entity_id, self.inner.snapshot(), self.is_active)
+    }
+
     pub fn fast_serialize(&self) -> Option<(T, bool)> {
         Some((self.inner.clone(), self.is_active))
     }
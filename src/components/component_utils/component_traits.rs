@@ -1,6 +1,6 @@
 use super::{
-    imgui_component_utils::*, Component, ComponentList, Entity, Name, PrefabMap, SerializationMarker,
-    SerializedEntity,
+    imgui_component_utils::*, CommandBuffer, Component, ComponentDatabase, ComponentList, Entity, Name,
+    PrefabMap, SceneMode, SerializablePrefabReference, SerializationMarker, SerializedEntity,
 };
 use imgui::Ui;
 
@@ -15,6 +15,21 @@ pub trait ComponentBounds {
     );
     fn uncommit_to_scene(&self, serialized_entity: &mut SerializedEntity);
     fn post_deserialization(&mut self, _: Entity, _: &ComponentList<SerializationMarker>) {}
+
+    /// Every Prefab this Component refers to, with the name of the field it's in. We
+    /// use these to find what uses a Prefab, and to point them at another one.
+    fn prefab_references(&mut self) -> Vec<(&'static str, &mut SerializablePrefabReference)> {
+        vec![]
+    }
+
+    /// An exact copy of this Component, for WorldSnapshots. That's usually just a Clone,
+    /// but some Clones make something new instead (like SerializationMarkers).
+    fn snapshot(&self) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        self.clone()
+    }
 }
 
 pub trait SerializableComponent:
@@ -29,6 +44,13 @@ pub trait SerializableComponent:
     const SERIALIZATION_NAME: once_cell::sync::Lazy<serde_yaml::Value>;
 }
 
+/// The ComponentDatabase implements this for every Component in the Component Registry,
+/// so generic code can find the ComponentList for a Component.
+pub trait ComponentListOf<T: ComponentBounds + Clone> {
+    fn component_list(&self) -> &ComponentList<T>;
+    fn component_list_mut(&mut self) -> &mut ComponentList<T>;
+}
+
 pub struct InspectorParameters<'a, 'b> {
     pub ui: &'b imgui::Ui<'a>,
     pub entities: &'b [Entity],
@@ -41,22 +63,27 @@ pub struct InspectorParameters<'a, 'b> {
 pub trait ComponentListBounds {
     fn expand_list(&mut self);
     fn unset(&mut self, index: &Entity) -> bool;
+    fn retire(&mut self, index: &Entity) -> bool;
     fn get_mut(&mut self, index: &Entity) -> Option<(&mut dyn ComponentBounds, bool)>;
     fn dump_to_log(&self, index: &Entity);
     fn clone_entity(&mut self, index: &Entity, new_entity: &Entity);
 
     // IMGUI
-    fn component_add_button(&mut self, index: &Entity, ui: &imgui::Ui<'_>);
+    // Adding and deleting go through the CommandBuffer, so Transforms get in and out of
+    // the Scene Graph and the Grid properly.
+    fn component_add_button(&mut self, index: &Entity, ui: &imgui::Ui<'_>, commands: &mut CommandBuffer);
     #[must_use]
     fn component_inspector(
         &mut self,
         index: &Entity,
         parent_sync_status: Option<ParentSyncStatus>,
+        scene_mode: SceneMode,
         entities: &[Entity],
         entity_names: &ComponentList<Name>,
         prefab_hashmap: &PrefabMap,
         ui: &imgui::Ui<'_>,
         is_open: bool,
+        commands: &mut CommandBuffer,
     ) -> Option<ComponentSerializationCommandType>;
 
     #[must_use]
@@ -101,6 +128,7 @@ pub trait ComponentListBounds {
 impl<T> ComponentListBounds for ComponentList<T>
 where
     T: ComponentBounds + SerializableComponent,
+    ComponentDatabase: ComponentListOf<T>,
 {
     fn expand_list(&mut self) {
         self.expand_list();
@@ -110,6 +138,10 @@ where
         self.unset(index)
     }
 
+    fn retire(&mut self, index: &Entity) -> bool {
+        self.retire(index)
+    }
+
     fn dump_to_log(&self, index: &Entity) {
         let comp_name = super::imgui_system::typed_text_ui::<T>();
         if let Some(comp) = self.get(index) {
@@ -125,12 +157,12 @@ where
             self.set_component(new_entity, new_component);
         }
     }
-    fn component_add_button(&mut self, index: &Entity, ui: &imgui::Ui<'_>) {
+    fn component_add_button(&mut self, index: &Entity, ui: &imgui::Ui<'_>, commands: &mut CommandBuffer) {
         if imgui::MenuItem::new(&imgui::ImString::new(super::imgui_system::typed_text_ui::<T>()))
             .enabled(self.get(index).is_none())
             .build(ui)
         {
-            self.set_component(index, T::default());
+            commands.add_component(*index, T::default());
         }
     }
 
@@ -138,19 +170,29 @@ where
         &mut self,
         entity: &Entity,
         parent_sync_status: Option<ParentSyncStatus>,
+        scene_mode: SceneMode,
         entities: &[Entity],
         entity_names: &ComponentList<Name>,
         prefab_hashmap: &PrefabMap,
         ui: &Ui<'_>,
         is_open: bool,
+        commands: &mut CommandBuffer,
     ) -> Option<ComponentSerializationCommandType> {
         if let Some(comp) = self.get_mut(entity) {
-            let ParentSyncStatus { serialized, prefab } = parent_sync_status.unwrap();
+            let ParentSyncStatus {
+                serialized,
+                prefab,
+                prefab_level,
+                overridden_fields,
+            } = parent_sync_status.unwrap();
 
             let (serialization_command, delete) = super::imgui_system::component_inspector_raw(
                 comp,
                 serialized,
                 prefab,
+                prefab_level,
+                &overridden_fields,
+                scene_mode,
                 entities,
                 entity_names,
                 prefab_hashmap,
@@ -161,7 +203,7 @@ where
             );
 
             if delete {
-                self.unset(entity);
+                commands.remove_component::<T>(*entity);
             }
 
             serialization_command
@@ -265,6 +307,11 @@ where
         );
     }
 
+    /// An exact copy of the whole List, for WorldSnapshots.
+    pub fn snapshot(&self) -> Self {
+        self.clone_with(Component::snapshot)
+    }
+
     /// Gets a mutable reference to the contained if it exists.
     /// Otherwise, it creates the contained using default and returns
     /// a mutable reference to that.
@@ -5,7 +5,8 @@ use super::{
 
 #[derive(Default)]
 pub struct DrawCommand<'a> {
-    pub game_world: Option<GameWorldDrawCommands<'a>>,
+    /// One per Scene on the SceneStack, drawn bottom to top.
+    pub game_worlds: Vec<GameWorldDrawCommands<'a>>,
     pub imgui: Option<ImGuiDrawCommands<'a>>,
 }
 
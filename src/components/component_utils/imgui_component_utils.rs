@@ -10,8 +10,11 @@ pub enum NameRequestedAction {
     Delete,
 
     PromoteToPrefab,
+    PromoteToVariant,
+    CreateVariant,
     UnpackPrefab,
     GoToPrefab,
+    FindUsages,
 
     LogEntity,
     LogSerializedEntity,
@@ -126,12 +129,28 @@ impl SyncStatus {
             }
         }
     }
+
+    /// Like `imgui_color`, but a Component synced to a Prefab is tinted by how far up the
+    /// Prefab chain its value comes from.
+    pub fn prefab_imgui_color(&self, scene_mode: super::SceneMode, prefab_level: Option<usize>) -> [f32; 4] {
+        match (self, prefab_level) {
+            (SyncStatus::Synced, Some(level)) if level > 0 && scene_mode == super::SceneMode::Draft => {
+                super::imgui_system::prefab_base_color(level)
+            }
+            _ => self.imgui_color(scene_mode),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct ParentSyncStatus {
     pub serialized: SyncStatus,
     pub prefab: SyncStatus,
+    /// Which Prefab in the chain the Component's value comes from. 0 is the Entity's own
+    /// Prefab, 1 is the Prefab that one is a variant of, and so on.
+    pub prefab_level: Option<usize>,
+    /// The paths of the fields which differ from the Prefab, like `sprite.inner.sprite_name`.
+    pub overridden_fields: Vec<String>,
 }
 
 impl ParentSyncStatus {
@@ -145,6 +164,8 @@ impl ParentSyncStatus {
         ParentSyncStatus {
             serialized: SyncStatus::new(comp, serialized_entity, should_have_serialized_entity),
             prefab: SyncStatus::new(comp, prefab_entity, should_have_prefab_entity),
+            prefab_level: None,
+            overridden_fields: vec![],
         }
     }
 }
@@ -222,13 +243,17 @@ pub struct ComponentSerializationCommand {
     pub command_type: ComponentSerializationCommandType,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum ComponentSerializationCommandType {
     Serialize,
     StopSerializing,
     Revert,
     ApplyOverrideToParentPrefab,
     RevertToParentPrefab,
+    /// Applies one field, by its path, to the Prefab.
+    ApplyFieldToParentPrefab(String),
+    /// Reverts one field, by its path, to the Prefab's.
+    RevertFieldToParentPrefab(String),
 }
 
 #[derive(Debug, Copy, Clone)]
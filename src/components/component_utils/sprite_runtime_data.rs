@@ -1,15 +1,9 @@
-use super::{
-    cardinals::{self, FacingHorizontal, FacingVertical},
-    Color, DrawOrder, Vec2,
-};
+use super::{Color, DrawOrder};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct SpriteRunningData {
     pub draw_order: DrawOrder,
-    pub facing_horizontal: FacingHorizontal,
-    pub facing_vertical: FacingVertical,
-    pub scale: Vec2,
     pub tint: Color,
     pub current_frame: usize,
     pub frame_time: f32,
@@ -19,9 +13,6 @@ pub struct SpriteRunningData {
 impl Default for SpriteRunningData {
     fn default() -> Self {
         Self {
-            scale: Vec2::ONE,
-            facing_horizontal: FacingHorizontal::default(),
-            facing_vertical: FacingVertical::default(),
             draw_order: DrawOrder::default(),
             tint: Color::default(),
 
@@ -34,10 +25,8 @@ impl Default for SpriteRunningData {
 
 impl SpriteRunningData {
     pub fn inspect(&mut self, ui: &imgui::Ui<'_>, uid: &str) {
-        cardinals::inspect_facing(ui, uid, &mut self.facing_horizontal, &mut self.facing_vertical);
         self.tint.inspect(ui, "Tint", uid);
         self.draw_order.inspect(ui, uid);
-        self.scale.inspector(ui, &imgui::im_str!("Scale##{}", uid));
 
         // FRAME TIME
         ui.input_float(&imgui::im_str!("Frame Time##{}", uid), &mut self.frame_time)
@@ -76,6 +76,13 @@ impl ComponentBounds for ConversantNPC {
         se.conversant_npc = None;
     }
 
+    fn prefab_references(&mut self) -> Vec<(&'static str, &mut SerializablePrefabReference)> {
+        vec![
+            ("ConversantNPC.initial_ui_prefab", &mut self.initial_ui_prefab),
+            ("ConversantNPC.text_ui_prefab", &mut self.text_ui_prefab),
+        ]
+    }
+
     fn post_deserialization(
         &mut self,
         _: super::Entity,
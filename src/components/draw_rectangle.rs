@@ -1,6 +1,6 @@
 use super::{
-    Color, ComponentBounds, DrawOrder, InspectorParameters, Rect, StandardQuad, StandardQuadFactory,
-    TextureDescription, Vec2,
+    Affine2, Color, ComponentBounds, DrawOrder, InspectorParameters, Rect, StandardQuad, StandardQuadFactory,
+    TextureDescription,
 };
 
 #[derive(Debug, Clone,SerializableComponent, PartialEq, Serialize, Deserialize, Default, typename::TypeName)]
@@ -60,13 +60,11 @@ impl ComponentBounds for DrawRectangle {
 }
 
 impl StandardQuadFactory for DrawRectangle {
-    fn to_standard_quad(&self, pos: Vec2) -> StandardQuad {
-        let translated_box: Rect = self.rect + pos;
-        let size = translated_box.size();
-
+    fn to_standard_quad(&self, transform: &Affine2) -> StandardQuad {
         StandardQuad {
-            pos: translated_box.min,
-            image_size: size,
+            transform: *transform,
+            pos: self.rect.min,
+            image_size: self.rect.size(),
             draw_order: self.draw_order,
             color: self.tint,
             texture_info: TextureDescription::White,
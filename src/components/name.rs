@@ -155,6 +155,18 @@ impl Name {
                                 }
                             }
                             prefab_kind => {
+                                if prefab_kind == PrefabStatus::PrefabInstance {
+                                    if MenuItem::new(&im_str!("Promote to Variant##{}", uid)).build(ui) {
+                                        res.requested_action = Some(NameRequestedAction::PromoteToVariant);
+                                        ui.close_current_popup();
+                                    }
+                                }
+
+                                if MenuItem::new(&im_str!("Create Variant##{}", uid)).build(ui) {
+                                    res.requested_action = Some(NameRequestedAction::CreateVariant);
+                                    ui.close_current_popup();
+                                }
+
                                 if MenuItem::new(&im_str!("Unpack Prefab##{}", uid))
                                     .enabled(prefab_kind == PrefabStatus::PrefabInstance)
                                     .build(ui)
@@ -172,6 +184,14 @@ impl Name {
                             res.requested_action = Some(NameRequestedAction::GoToPrefab);
                             ui.close_current_popup();
                         }
+
+                        if MenuItem::new(&im_str!("Find Usages##{}", uid))
+                            .enabled(nip.prefab_status != PrefabStatus::None)
+                            .build(ui)
+                        {
+                            res.requested_action = Some(NameRequestedAction::FindUsages);
+                            ui.close_current_popup();
+                        }
                     });
 
                     ui.separator();
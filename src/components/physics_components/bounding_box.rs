@@ -1,12 +1,13 @@
-use super::{ComponentBounds, InspectorParameters, Rect, Vec2};
+use super::{Affine2, ComponentBounds, InspectorParameters, Rect, Vec2};
 
-#[derive(
-    Debug, Clone, SerializableComponent, Copy, Default, PartialEq, Serialize, Deserialize, typename::TypeName,
-)]
+#[derive(Debug, Clone, SerializableComponent, Copy, Default, Serialize, Deserialize, typename::TypeName)]
 #[serde(default)]
 pub struct BoundingBox {
     pub rect: Rect,
     pub bind_to_sprite: bool,
+    /// `rect` put through our Transform, and the `rect` it was made from.
+    #[serde(skip)]
+    world_rect: Option<(Rect, Rect)>,
 }
 
 impl BoundingBox {
@@ -14,8 +15,32 @@ impl BoundingBox {
         Self {
             rect: Rect::new(min, max),
             bind_to_sprite: false,
+            world_rect: None,
         }
     }
+
+    /// Where we are in the world, as of the last `sync_world_rect`. Until then, it's just `rect`.
+    pub fn world_rect(&self) -> Rect {
+        self.world_rect
+            .map(|(_, world_rect)| world_rect)
+            .unwrap_or(self.rect)
+    }
+
+    /// Puts `rect` through our Transform again, if it moved or `rect` changed since last time.
+    pub fn sync_world_rect(&mut self, world_transform: &Affine2, transform_changed: bool) {
+        let rect_changed = self.world_rect.map_or(true, |(rect, _)| rect != self.rect);
+
+        if transform_changed || rect_changed {
+            self.world_rect = Some((self.rect, self.rect.transformed(world_transform)));
+        }
+    }
+}
+
+// The world rect is only a cache, so it doesn't count.
+impl PartialEq for BoundingBox {
+    fn eq(&self, other: &Self) -> bool {
+        self.rect == other.rect && self.bind_to_sprite == other.bind_to_sprite
+    }
 }
 
 impl ComponentBounds for BoundingBox {
@@ -41,11 +41,27 @@ impl PrefabMarker {
 
 impl ComponentBounds for PrefabMarker {
     fn entity_inspector(&mut self, ip: InspectorParameters<'_, '_>) {
-        if let Some(serialized_name) = &ip.prefabs.get(&self.main_id).unwrap().root_entity().name {
-            ip.ui
-                .text(imgui::im_str!("Original Prefab: {}", serialized_name.inner.name));
-        } else {
-            ip.ui.text(imgui::im_str!("Original Prefab: {}", self.main_id));
+        let prefab_name = |main_id: &Uuid| {
+            ip.prefabs
+                .get(main_id)
+                .and_then(|prefab| prefab.root_entity().name.as_ref())
+                .map_or_else(|| main_id.to_string(), |name| name.inner.name.clone())
+        };
+
+        ip.ui
+            .text(imgui::im_str!("Original Prefab: {}", prefab_name(&self.main_id)));
+
+        // Variants and nested Prefabs go on up the chain
+        match super::prefab_system::prefab_chain(ip.prefabs, self.main_id, self.sub_id) {
+            Ok(chain) => {
+                for (base, _) in chain.iter().skip(1) {
+                    ip.ui
+                        .text(imgui::im_str!("Inherits From: {}", prefab_name(&base.main_id)));
+                }
+            }
+            Err(e) => ip
+                .ui
+                .text_colored(super::imgui_system::red_warning_color(), imgui::im_str!("{}", e)),
         }
     }
 
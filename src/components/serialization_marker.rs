@@ -1,5 +1,5 @@
 use super::{
-    imgui_component_utils::SyncStatus, serialization_util, ComponentBounds, InspectorParameters,
+    imgui_component_utils::SyncStatus, serialization_util, ComponentBounds, InspectorParameters, Scene,
     SerializedEntity,
 };
 use imgui::*;
@@ -18,6 +18,8 @@ struct SerializedEntityCache {
     last_save_time: Instant,
     serialized_entity_on_disk: Option<SerializedEntity>,
     force_recache: bool,
+    /// The last SyncStatus we worked out. See `cached_serialization_status`.
+    sync_status: Option<SyncStatus>,
 }
 
 impl Default for SerializedEntityCache {
@@ -26,6 +28,7 @@ impl Default for SerializedEntityCache {
             serialized_entity_on_disk: None,
             force_recache: true,
             last_save_time: Instant::now(),
+            sync_status: None,
         }
     }
 }
@@ -53,14 +56,14 @@ impl SerializationMarker {
     /// This is a cache of our Serialized Data. We'll try to get one, but
     /// our serialization data might have been destroyed. If a value is retreived,
     /// it is no more than 5 seconds old.
-    pub fn cached_serialized_entity(&mut self) -> Option<&SerializedEntity> {
-        self.update_cache();
+    pub fn cached_serialized_entity(&mut self, scene: &Scene) -> Option<&SerializedEntity> {
+        self.update_cache(scene);
         self.cache.serialized_entity_on_disk.as_ref()
     }
 
     #[must_use]
-    pub fn entity_inspector_results(&mut self, ip: InspectorParameters<'_, '_>) -> bool {
-        self.update_cache();
+    pub fn entity_inspector_results(&mut self, scene: &Scene, ip: InspectorParameters<'_, '_>) -> bool {
+        self.update_cache(scene);
 
         let mut serialize_entity = false;
 
@@ -91,11 +94,12 @@ impl SerializationMarker {
 
     pub fn get_serialization_status(
         &mut self,
+        scene: &Scene,
         current_serialized_entity: Option<&SerializedEntity>,
     ) -> SyncStatus {
-        self.update_cache();
+        self.update_cache(scene);
 
-        if let Some(se_on_disk) = &self.cache.serialized_entity_on_disk {
+        let sync_status = if let Some(se_on_disk) = &self.cache.serialized_entity_on_disk {
             if let Some(serialized_entity) = current_serialized_entity {
                 if se_on_disk == serialized_entity {
                     SyncStatus::Synced
@@ -108,12 +112,30 @@ impl SerializationMarker {
             }
         } else {
             SyncStatus::OutofSync
-        }
+        };
+
+        self.cache.sync_status = Some(sync_status);
+        sync_status
+    }
+
+    /// Working out our SyncStatus means serializing the whole Entity, so we keep the last
+    /// one around. It's forgotten when what's on disk changes, or with `forget_serialization_status`
+    /// when the Entity might have. Moving is checked with the Transform's change flag.
+    pub fn cached_serialization_status(&mut self, scene: &Scene) -> Option<SyncStatus> {
+        self.update_cache(scene);
+        self.cache.sync_status
     }
 
-    fn imgui_serialization(&mut self) {
-        match serialization_util::entities::load_committed_entity(self) {
+    pub fn forget_serialization_status(&mut self) {
+        self.cache.sync_status = None;
+    }
+
+    fn imgui_serialization(&mut self, scene: &Scene) {
+        match serialization_util::entities::load_committed_entity(scene, self) {
             Ok(maybe_serialized_entity) => {
+                if maybe_serialized_entity != self.cache.serialized_entity_on_disk {
+                    self.cache.sync_status = None;
+                }
                 self.cache.serialized_entity_on_disk = maybe_serialized_entity;
             }
             Err(e) => {
@@ -122,7 +144,7 @@ impl SerializationMarker {
         }
     }
 
-    fn update_cache(&mut self) {
+    fn update_cache(&mut self, scene: &Scene) {
         let reload_se = {
             let time_since: Duration = Instant::now() - self.cache.last_save_time;
             time_since.as_secs() > 5
@@ -130,7 +152,7 @@ impl SerializationMarker {
 
         if reload_se || self.cache.force_recache {
             self.cache.force_recache = false;
-            self.imgui_serialization();
+            self.imgui_serialization(scene);
         }
     }
 }
@@ -165,6 +187,11 @@ impl ComponentBounds for SerializationMarker {
     fn uncommit_to_scene(&self, _: &mut SerializedEntity) {
         unimplemented!();
     }
+
+    /// Our Clone gives out a new id, but a snapshot has to keep ours.
+    fn snapshot(&self) -> Self {
+        SerializationMarker::with_id(self.id)
+    }
 }
 
 impl super::SerializableComponent for SerializationMarker {
@@ -0,0 +1,104 @@
+use super::{ComponentBounds, InspectorParameters};
+use imgui::im_str;
+
+/// Any number of strings to find an Entity by. Look them up with the `TagIndex`.
+#[derive(Debug, SerializableComponent, Clone, Eq, Serialize, Deserialize, typename::TypeName)]
+#[serde(default)]
+pub struct Tags {
+    tags: Vec<String>,
+    /// Our tags changed since the TagIndex last looked. New Tags (including freshly
+    /// loaded ones) start out dirty, since it's never seen them.
+    #[serde(skip, default = "new_tags_are_dirty")]
+    dirty: bool,
+}
+
+fn new_tags_are_dirty() -> bool {
+    true
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self {
+            tags: vec![],
+            dirty: true,
+        }
+    }
+}
+
+impl Tags {
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+}
+
+// The TagIndex's bookkeeping isn't part of what we are.
+impl PartialEq for Tags {
+    fn eq(&self, other: &Self) -> bool {
+        self.tags == other.tags
+    }
+}
+
+impl ComponentBounds for Tags {
+    fn entity_inspector(&mut self, ip: InspectorParameters<'_, '_>) {
+        let mut remove_this_tag = None;
+
+        for (i, tag) in self.tags.iter_mut().enumerate() {
+            let mut im_tag = imgui::ImString::new(tag.as_str());
+            if ip
+                .ui
+                .input_text(&im_str!("##Tag{}{}", i, ip.uid), &mut im_tag)
+                .resize_buffer(true)
+                .build()
+            {
+                *tag = im_tag.to_string();
+                self.dirty = true;
+            }
+
+            ip.ui.same_line(0.0);
+            if ip.ui.button(&im_str!("Remove##{}{}", i, ip.uid), [0.0, 0.0]) {
+                remove_this_tag = Some(i);
+            }
+        }
+
+        if let Some(i) = remove_this_tag {
+            self.tags.remove(i);
+            self.dirty = true;
+        }
+
+        if ip.ui.button(&im_str!("Add Tag##{}", ip.uid), [0.0, 0.0]) {
+            self.tags.push(String::new());
+            self.dirty = true;
+        }
+    }
+
+    fn is_serialized(&self, serialized_entity: &super::SerializedEntity, active: bool) -> bool {
+        serialized_entity
+            .tags
+            .as_ref()
+            .map_or(false, |s| s.active == active && &s.inner == self)
+    }
+
+    fn commit_to_scene(
+        &self,
+        se: &mut super::SerializedEntity,
+        active: bool,
+        _: &super::ComponentList<super::SerializationMarker>,
+    ) {
+        se.tags = Some(super::SerializedComponent {
+            inner: self.clone(),
+            active,
+        });
+    }
+
+    fn uncommit_to_scene(&self, se: &mut super::SerializedEntity) {
+        se.tags = None;
+    }
+}
@@ -1,7 +1,7 @@
 use super::{
     component_utils::{TextHorizontalAlign, TextVerticalAlign},
     fonts::FontName,
-    imgui_system, Color, ComponentBounds, DrawOrder, InspectorParameters, StandardQuad, Vec2,
+    imgui_system, Affine2, Color, ComponentBounds, DrawOrder, InspectorParameters, StandardQuad, Vec2,
 };
 
 #[derive(
@@ -93,11 +93,11 @@ impl ComponentBounds for TextSource {
 }
 
 impl TextSource {
-    pub fn prepare_standard_quad(&self, pos: Vec2, quad: &StandardQuad) -> StandardQuad {
+    pub fn prepare_standard_quad(&self, transform: &Affine2, quad: &StandardQuad) -> StandardQuad {
         let mut ret = quad.clone();
 
+        ret.transform = *transform;
         ret.pos /= self.screen_scale;
-        ret.pos += pos;
         ret.image_size /= self.screen_scale;
         ret.color = self.color;
         ret.draw_order = self.draw_order;
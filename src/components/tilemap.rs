@@ -1,6 +1,6 @@
 use super::{
     component_serialization::TilemapSerialized, component_utils::EditingMode, imgui_system,
-    serialization_util, tile_resources::*, Color, ComponentBounds, DrawOrder, InspectorParameters,
+    serialization_util, tile_resources::*, Affine2, Color, ComponentBounds, DrawOrder, InspectorParameters,
     PositionalRect, StandardQuad, TextureDescription, Tile, Vec2, Vec2Int,
 };
 
@@ -19,7 +19,7 @@ pub struct Tilemap {
 }
 
 impl Tilemap {
-    pub fn create_tile_quads(&self, root_position: Vec2, quad_buffer: &mut Vec<StandardQuad>) {
+    pub fn create_tile_quads(&self, transform: &Affine2, quad_buffer: &mut Vec<StandardQuad>) {
         if let Some(tileset) = &self.tileset {
             if tileset.visual_data.sprite_data.is_some() {
                 for (i, this_tile) in self.tiles.iter().enumerate() {
@@ -27,9 +27,10 @@ impl Tilemap {
                         if let Some((texture_info, tile_native_size)) =
                             tileset.visual_data.tileset_texture_description(this_tile.index)
                         {
-                            let pos = self.get_tile_position(i, root_position, tile_native_size);
+                            let pos = self.get_tile_position(i, Vec2::ZERO, tile_native_size);
 
                             let standard_quad = StandardQuad {
+                                transform: *transform,
                                 texture_info: TextureDescription::Standard(texture_info),
                                 pos,
                                 color: self.tint,
@@ -1,17 +1,24 @@
 use super::{
-    component_utils::RawComponent, ComponentBounds, ComponentList, Entity, GraphNode, InspectorParameters,
-    TransformParent, Vec2,
+    component_utils::RawComponent, Affine2, ComponentBounds, ComponentList, Entity, GraphNode,
+    InspectorParameters, TransformParent, Vec2,
 };
 
-#[derive(Debug, SerializableComponent, Clone, Default, Serialize, Deserialize, typename::TypeName)]
+#[derive(Debug, SerializableComponent, Clone, Serialize, Deserialize, typename::TypeName)]
 #[serde(default)]
 pub struct Transform {
     local_position: Vec2,
+    /// Counter-clockwise, in radians.
+    local_rotation: f32,
+    local_scale: Vec2,
     world_position: Vec2,
-    // orientation
-    // probably some other garbanzo
+    #[serde(skip)]
+    world_transform: Affine2,
+    /// Our local transform changed, so our world transform (and our children's) is stale.
     #[serde(skip)]
     dirty: bool,
+    /// Our world transform changed since the editor last looked, which is once a frame.
+    #[serde(skip)]
+    changed: bool,
     parent: TransformParent,
 }
 
@@ -23,8 +30,12 @@ impl Transform {
     pub fn new(local_position: Vec2) -> Self {
         Transform {
             local_position,
+            local_rotation: 0.0,
+            local_scale: Vec2::ONE,
             world_position: Vec2::ZERO,
+            world_transform: Affine2::IDENTITY,
             dirty: true,
+            changed: false,
             parent: TransformParent::blank(),
         }
     }
@@ -55,10 +66,33 @@ impl Transform {
         }
     }
 
+    /// Takes us out of the Scene Graph entirely. Our parent, Root or otherwise, forgets us.
+    pub fn remove_from_scene_graph(&mut self, my_id: Entity) {
+        self.remove_self_from_parent(my_id);
+        self.parent = TransformParent::blank();
+        self.dirty = true;
+    }
+
+    /// True if we're in the Scene Graph at all, including as a child of the Root.
+    pub fn in_scene_graph(&self) -> bool {
+        self.parent.target.graph_node.is_null() == false
+    }
+
     pub fn parent_exists(&self) -> bool {
         self.parent.target.is_real()
     }
 
+    /// The Entity of our parent GraphNode. This is None if we're a child of the Root.
+    pub fn parent_entity(&self) -> Option<Entity> {
+        self.parent.target.entity
+    }
+
+    /// Points us at a new copy of our parent's GraphNode. Our parent's children aren't
+    /// touched, so only use this when the GraphNodes have moved in memory.
+    pub fn relink_parent(&mut self, graph_node: *mut GraphNode) {
+        self.parent.target.graph_node = graph_node;
+    }
+
     pub fn parent_mut(&mut self) -> Option<&mut GraphNode> {
         self.parent.parent_mut()
     }
@@ -69,6 +103,7 @@ impl Transform {
 
     pub fn set_local_position(&mut self, new_local_position: Vec2) {
         self.local_position = new_local_position;
+        self.dirty = true;
     }
 
     pub fn local_position(&self) -> Vec2 {
@@ -77,16 +112,74 @@ impl Transform {
 
     pub fn edit_local_position(&mut self, f: impl Fn(Vec2) -> Vec2) {
         self.local_position = f(self.local_position);
+        self.dirty = true;
     }
 
     pub fn local_position_fast(clist: &ComponentList<Transform>, entity_id: &Entity) -> Option<Vec2> {
         clist.get(entity_id).as_ref().map(|&t| t.inner().local_position)
     }
 
-    pub fn update_world_position(&mut self, parent_position: Vec2) -> Vec2 {
-        self.world_position = self.local_position + parent_position;
+    pub fn local_rotation(&self) -> f32 {
+        self.local_rotation
+    }
+
+    pub fn set_local_rotation(&mut self, new_local_rotation: f32) {
+        self.local_rotation = new_local_rotation;
+        self.dirty = true;
+    }
+
+    pub fn local_scale(&self) -> Vec2 {
+        self.local_scale
+    }
+
+    pub fn set_local_scale(&mut self, new_local_scale: Vec2) {
+        self.local_scale = new_local_scale;
+        self.dirty = true;
+    }
+
+    /// Our position, rotation, and scale, relative to our parent.
+    pub fn local_transform(&self) -> Affine2 {
+        Affine2::from_scale_rotation_translation(self.local_scale, self.local_rotation, self.local_position)
+    }
+
+    /// Our position, rotation, and scale in the world, as of the last time we walked
+    /// the Scene Graph.
+    pub fn world_transform(&self) -> Affine2 {
+        self.world_transform
+    }
+
+    /// True if our world transform needs to be recomputed.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// True if our world transform changed since the editor last looked. That covers this
+    /// frame's ticks so far, and the end of the last frame. Systems can use this to skip
+    /// everything which stood still.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn clear_changed(&mut self) {
+        self.changed = false;
+    }
+
+    pub fn update_world_transform(&mut self, parent_transform: &Affine2) -> Affine2 {
+        let world_transform = *parent_transform * self.local_transform();
+        if world_transform != self.world_transform {
+            self.changed = true;
+        }
+
+        self.world_transform = world_transform;
+        self.world_position = world_transform.translation;
         self.dirty = false;
-        self.world_position()
+        self.world_transform
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::new(Vec2::ZERO)
     }
 }
 
@@ -100,6 +193,14 @@ impl ComponentBounds for Transform {
             self.dirty = true;
         }
 
+        if AngleSlider::new(&im_str!("Rotation##{}", ip.uid)).build(ip.ui, &mut self.local_rotation) {
+            self.dirty = true;
+        }
+
+        if self.local_scale.inspector(ip.ui, &im_str!("Scale##{}", ip.uid)) {
+            self.dirty = true;
+        }
+
         self.world_position
             .no_interact_inspector(ip.ui, &im_str!("World Position##{}", ip.uid));
     }
@@ -133,20 +234,14 @@ impl ComponentBounds for Transform {
     fn uncommit_to_scene(&self, se: &mut super::SerializedEntity) {
         se.transform = None;
     }
-    
-    fn post_deserialization(
-        &mut self,
-        entity: super::Entity,
-        serialization_markers: &super::ComponentList<super::SerializationMarker>,
-    ) {
-        super::scene_graph::add_to_scene_graph((self, entity), serialization_markers);
-    }
 }
 
 impl PartialEq for Transform {
     fn eq(&self, other: &Transform) -> bool {
         if self.parent == other.parent {
             self.local_position == other.local_position
+                && self.local_rotation == other.local_rotation
+                && self.local_scale == other.local_scale
         } else {
             false
         }
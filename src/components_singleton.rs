@@ -1,5 +1,19 @@
 use super::*;
 
+/// This is the Singleton Registry -- every SingletonComponent in the game, and the Marker
+/// its associated Entity goes by. Like the Component Registry, it hands the list to
+/// `$generator`, which is `impl_singleton_database` or `inspect_singletons`.
+///
+/// To add a new singleton, give it `SingletonBounds` and `Default`, add a Marker for it and
+/// add a line here. Scenes saved before it existed get it from `default_singleton_data.yaml`.
+macro_rules! singleton_registry {
+    ($generator:ident) => {
+        $generator! {
+            camera: Camera => Camera,
+        }
+    };
+}
+
 mod camera;
 mod markers;
 mod rendering_utility;
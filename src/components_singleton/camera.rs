@@ -11,10 +11,6 @@ pub struct Camera {
 }
 
 impl Camera {
-    pub fn initialize_with_hwi(&mut self, hwi: &HardwareInterface) {
-        self.display_size = Some(hwi.window.get_window_size());
-    }
-
     pub fn ingame_camera_size(&self) -> Vec2 {
         self.native_resolution / self.zoom_level
     }
@@ -68,6 +64,10 @@ impl Default for Camera {
 }
 
 impl SingletonBounds for Camera {
+    fn initialize_with_hwi(&mut self, hwi: &HardwareInterface) {
+        self.display_size = Some(hwi.window.get_window_size());
+    }
+
     fn entity_inspector(&mut self, inspector_parameters: InspectorParameters<'_, '_>) {
         let InspectorParameters { uid, ui, .. } = inspector_parameters;
 
@@ -1,4 +1,4 @@
-use super::{InspectorParameters, Marker};
+use super::{HardwareInterface, InspectorParameters, Marker};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SingletonComponent<T: SingletonBounds> {
@@ -26,4 +26,7 @@ impl<T: SingletonBounds> SingletonComponent<T> {
 
 pub trait SingletonBounds {
     fn entity_inspector(&mut self, inspector_parameters: InspectorParameters<'_, '_>);
+
+    /// Called once the window and the renderer are up.
+    fn initialize_with_hwi(&mut self, _hwi: &HardwareInterface) {}
 }
@@ -1,5 +1,5 @@
 use super::{
-    serialization_util, Camera, Entity, Marker, RenderingUtility, ResourcesDatabase, SingletonBounds,
+    serialization_util, Camera, Entity, Marker, RenderingUtility, ResourcesDatabase, Scene, SingletonBounds,
     SingletonComponent,
 };
 use anyhow::{Error, Result as AnyResult};
@@ -7,19 +7,52 @@ use std::collections::HashMap;
 
 pub type AssociatedEntityMap = HashMap<Marker, Entity>;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SingletonDatabase {
-    pub camera: SingletonComponent<Camera>,
-    #[serde(skip)]
-    pub rendering_utility: RenderingUtility,
-    #[serde(skip)]
-    pub associated_entities: AssociatedEntityMap,
+/// Generates `SingletonDatabase`, its `Default`, `snapshot` and `initialize_singletons` from
+/// the Singleton Registry. Invoke it through `singleton_registry!`.
+macro_rules! impl_singleton_database {
+    ($($singleton_name:ident: $singleton:ty => $marker:ident,)*) => {
+        #[derive(Debug, Serialize, Deserialize)]
+        pub struct SingletonDatabase {
+            $(pub $singleton_name: SingletonComponent<$singleton>,)*
+            #[serde(skip)]
+            pub rendering_utility: RenderingUtility,
+            #[serde(skip)]
+            pub associated_entities: AssociatedEntityMap,
+        }
+
+        impl SingletonDatabase {
+            /// Copies everything but the RenderingUtility, which is only runtime data.
+            pub fn snapshot(&self) -> SingletonDatabase {
+                SingletonDatabase {
+                    $($singleton_name: self.$singleton_name.clone(),)*
+                    rendering_utility: RenderingUtility::default(),
+                    associated_entities: self.associated_entities.clone(),
+                }
+            }
+
+            fn initialize_singletons(&mut self, hwi: &super::HardwareInterface) {
+                $(self.$singleton_name.inner_mut().initialize_with_hwi(hwi);)*
+            }
+        }
+
+        impl Default for SingletonDatabase {
+            fn default() -> Self {
+                SingletonDatabase {
+                    $($singleton_name: SingletonComponent::new(Marker::$marker, <$singleton>::default()),)*
+                    rendering_utility: RenderingUtility::default(),
+                    associated_entities: HashMap::new(),
+                }
+            }
+        }
+    };
 }
 
+singleton_registry!(impl_singleton_database);
+
 impl SingletonDatabase {
-    pub fn new(marker_map: AssociatedEntityMap) -> Result<SingletonDatabase, Error> {
+    pub fn new(scene: &Scene, marker_map: AssociatedEntityMap) -> Result<SingletonDatabase, Error> {
         let mut serialized_singletons: SingletonDatabase =
-            serialization_util::singleton_components::load_singleton_database()?;
+            serialization_util::singleton_components::load_singleton_database(scene)?;
 
         serialized_singletons.associated_entities = marker_map;
         Ok(serialized_singletons)
@@ -36,6 +69,7 @@ impl SingletonDatabase {
     }
 
     pub fn edit_serialized_singleton_database<T: SingletonBounds, F>(
+        scene: &Scene,
         live_component: &mut SingletonComponent<T>,
         edit_function: F,
     ) -> AnyResult<()>
@@ -43,11 +77,18 @@ impl SingletonDatabase {
         F: Fn(&mut SingletonDatabase, &mut SingletonComponent<T>),
     {
         let mut serialized_singletons: SingletonDatabase =
-            serialization_util::singleton_components::load_singleton_database()?;
+            serialization_util::singleton_components::load_singleton_database(scene)?;
 
         edit_function(&mut serialized_singletons, live_component);
 
-        serialization_util::singleton_components::serialize_singleton_database(&serialized_singletons)
+        serialization_util::singleton_components::serialize_singleton_database(scene, &serialized_singletons)
+    }
+
+    /// Swaps in a snapshot, keeping our RenderingUtility.
+    pub fn restore(&mut self, snapshot: SingletonDatabase) {
+        let rendering_utility = std::mem::take(&mut self.rendering_utility);
+        *self = snapshot;
+        self.rendering_utility = rendering_utility;
     }
 
     pub fn initialize_with_runtime_resources(
@@ -56,17 +97,6 @@ impl SingletonDatabase {
         hwi: &super::HardwareInterface,
     ) {
         self.rendering_utility.initialize(resources);
-        self.camera.inner_mut().initialize_with_hwi(hwi);
-    }
-}
-
-impl Default for SingletonDatabase {
-    fn default() -> Self {
-        SingletonDatabase {
-            // @update_singletons
-            camera: SingletonComponent::new(Marker::Camera, Camera::default()),
-            rendering_utility: RenderingUtility::default(),
-            associated_entities: HashMap::new(),
-        }
+        self.initialize_singletons(hwi);
     }
 }
@@ -4,8 +4,12 @@ use super::{
     entities::EntityAllocator,
     hardware_interfaces::HardwareInterface,
     resources::{PrefabMap, ResourcesDatabase},
-    systems::*,
-    ActionMap, GameWorldDrawCommands,
+    systems::{
+        scene_system::SceneState,
+        scheduler::{RunsIn, Scheduler, Stage},
+        *,
+    },
+    CommandBuffer, Events, GameWorldDrawCommands, LoadError, Scene, SceneGraph, TagIndex,
 };
 use anyhow::Error;
 
@@ -14,26 +18,52 @@ pub struct Ecs {
     pub singleton_database: SingletonDatabase,
     pub entities: Vec<Entity>,
     pub entity_allocator: EntityAllocator,
+    pub commands: CommandBuffer,
+    pub events: Events,
+    pub scene_graph: SceneGraph,
+    pub scene_state: SceneState,
+    pub tag_index: TagIndex,
+    /// Entities in the Scene's file which we couldn't load.
+    pub load_errors: Vec<LoadError>,
+    /// Entities which changed on disk while they had unsaved edits, so we kept the edits.
+    pub reload_conflicts: Vec<Entity>,
 }
 
 impl Ecs {
-    pub fn new(prefabs: &PrefabMap) -> Result<Self, Error> {
+    pub fn new(scene: Scene, prefabs: &PrefabMap) -> Result<Self, Error> {
         // Es and Cs
         let mut entity_allocator = EntityAllocator::new();
         let mut entities = Vec::new();
+        let mut scene_graph = SceneGraph::new();
 
         // Deserialize Entities and Singletons
         let mut marker_map = std::collections::HashMap::new();
-        let component_database =
-            ComponentDatabase::new(&mut entity_allocator, &mut entities, &mut marker_map, prefabs)?;
+        let mut load_errors = vec![];
+        let mut component_database = ComponentDatabase::new(
+            &scene,
+            &mut entity_allocator,
+            &mut entities,
+            &mut marker_map,
+            &mut scene_graph,
+            prefabs,
+            &mut load_errors,
+        )?;
 
-        let singleton_database = SingletonDatabase::new(marker_map)?;
+        let singleton_database = SingletonDatabase::new(&scene, marker_map)?;
+        let tag_index = TagIndex::new(&mut component_database.tags);
 
         Ok(Ecs {
             entities,
             entity_allocator,
             component_database,
             singleton_database,
+            commands: CommandBuffer::default(),
+            events: Events::default(),
+            scene_graph,
+            scene_state: SceneState::new(scene),
+            tag_index,
+            load_errors,
+            reload_conflicts: vec![],
         })
     }
 
@@ -64,24 +94,92 @@ impl Ecs {
         Ok(())
     }
 
-    pub fn update(&mut self, grid: &mut grid_system::Grid, actions: &ActionMap) -> Result<(), Error> {
-        // // Player Stuff
-        player_system::player_update(
-            &mut self.component_database.players,
-            &mut self.component_database.sprites,
-            &mut self.component_database.velocities,
-            actions,
+    /// This is the order the game's systems run in. See `scheduler` for the Stages.
+    pub fn register_systems(scheduler: &mut Scheduler) {
+        // Input
+        scheduler.add_system("player_update", Stage::Input, RunsIn::PLAYING, |ctx| {
+            player_system::player_update(
+                &mut ctx.ecs.component_database.players,
+                &mut ctx.ecs.component_database.sprites,
+                &mut ctx.ecs.component_database.velocities,
+                ctx.action_map,
+                &mut ctx.ecs.events.player_switched,
+            );
+        });
+
+        // Gameplay
+        scheduler.add_system("update_grid_positions", Stage::Gameplay, RunsIn::PLAYING, |ctx| {
+            grid_system::update_grid_positions(ctx.ecs, ctx.grid);
+        });
+
+        scheduler.add_system("update_follows", Stage::Gameplay, RunsIn::PLAYING, |ctx| {
+            follow_system::update_follows(
+                &ctx.ecs.component_database.follows,
+                &mut ctx.ecs.component_database.transforms,
+                &ctx.ecs.component_database.names,
+                &ctx.ecs.entity_allocator,
+                ctx.delta_time,
+            );
+        });
+
+        // Post Gameplay
+        scheduler.add_system("update_sprites", Stage::PostGameplay, RunsIn::PLAYING, |ctx| {
+            sprite_system::update_sprites(
+                &mut ctx.ecs.component_database.sprites,
+                ctx.resources,
+                ctx.delta_time,
+            );
+        });
+
+        scheduler.add_system(
+            "update_bounding_boxes",
+            Stage::PostGameplay,
+            RunsIn::PLAYING,
+            |ctx| {
+                physics_system::update_bounding_boxes(
+                    &mut ctx.ecs.component_database.bounding_boxes,
+                    &ctx.ecs.component_database.sprites,
+                    ctx.resources,
+                );
+            },
         );
 
-        // Movement Stuff
-        grid_system::update_grid_positions(self, grid);
+        scheduler.add_system(
+            "cross_cutting_system",
+            Stage::PostGameplay,
+            RunsIn::PLAYING,
+            |ctx| {
+                cross_cutting_system::cross_cutting_system(ctx.ecs);
+            },
+        );
 
-        Ok(())
-    }
+        scheduler.add_system(
+            "request_solved_level_scenes",
+            Stage::PostGameplay,
+            RunsIn::PLAYING,
+            |ctx| {
+                scene_system::request_solved_level_scenes(
+                    &ctx.ecs.events.level_solved,
+                    &ctx.ecs.component_database.scene_switchers,
+                    &mut ctx.ecs.events.scene_requested,
+                );
+            },
+        );
+
+        scheduler.add_system(
+            "handle_scene_requests",
+            Stage::PostGameplay,
+            RunsIn::PLAYING,
+            |ctx| {
+                scene_system::handle_scene_requests(
+                    &ctx.ecs.events.scene_requested,
+                    &mut ctx.ecs.scene_state,
+                );
+            },
+        );
 
-    pub fn update_resources(&mut self, resources: &ResourcesDatabase, delta_time: f32) {
-        sprite_system::update_sprites(&mut self.component_database.sprites, resources, delta_time);
-        cross_cutting_system::cross_cutting_system(self, resources);
+        // Pre Render has no systems yet, but its sync point still propagates anything
+        // the editor moved this frame.
     }
 
     pub fn render<'a, 'b>(
@@ -89,7 +187,7 @@ impl Ecs {
         draw_commands: &'b mut DrawCommand<'a>,
         resources: &'a ResourcesDatabase,
     ) {
-        draw_commands.game_world = Some(GameWorldDrawCommands {
+        draw_commands.game_worlds.push(GameWorldDrawCommands {
             text_sources: &self.component_database.text_sources,
             sprites: &self.component_database.sprites,
             rects: &self.component_database.draw_rectangles,
@@ -160,9 +258,25 @@ impl Ecs {
         )
     }
 
+    /// Every Entity with this tag. Tags added this Stage won't show up until it's over.
+    pub fn find_tagged(&self, tag: &str) -> &[Entity] {
+        self.tag_index.entities_with(tag)
+    }
+
+    /// Finds an Entity by the Names on the way down to it in the Scene Graph, like
+    /// `"Level/Walls/NorthWall"`.
+    pub fn find_by_path(&self, path: &str) -> Option<Entity> {
+        self.scene_graph.find_by_path(
+            path,
+            &self.component_database.names,
+            &self.component_database.graph_nodes,
+        )
+    }
+
     pub fn clone_entity(&mut self, original: &Entity) -> Entity {
         let new_entity = self.create_entity();
-        self.component_database.clone_components(original, &new_entity);
+        self.component_database
+            .clone_components(original, &new_entity, &mut self.scene_graph);
 
         new_entity
     }
@@ -35,6 +35,7 @@ impl PartialOrd for GenerationalIndex {
     }
 }
 
+#[derive(Clone)]
 pub struct AllocatorEntry {
     is_live: bool,
     generation: u64,
@@ -48,6 +49,7 @@ impl AllocatorEntry {
     }
 }
 
+#[derive(Clone)]
 pub struct GenerationalIndexAllocator {
     entries: Vec<AllocatorEntry>,
     free: Vec<usize>,
@@ -86,11 +88,11 @@ impl GenerationalIndexAllocator {
     }
 
     /// Returns true if the index was allocated, and is now
-    /// deallocated.
+    /// deallocated. Stale indices, from an older generation, are left alone.
     #[allow(dead_code)]
     pub fn deallocate(&mut self, index: &GenerationalIndex) -> bool {
         let entry = &mut self.entries[index.index];
-        if entry.is_live == false {
+        if entry.is_live == false || entry.generation != index.generation {
             false
         } else {
             entry.is_live = false;
@@ -100,12 +102,13 @@ impl GenerationalIndexAllocator {
         }
     }
 
-    #[allow(dead_code)]
+    /// Returns true if the index is allocated, and from the current generation.
     pub fn is_live(&self, index: GenerationalIndex) -> bool {
         if index.index >= self.entries.len() {
             false
         } else {
-            self.entries[index.index].is_live
+            let entry = &self.entries[index.index];
+            entry.is_live && entry.generation == index.generation
         }
     }
 }
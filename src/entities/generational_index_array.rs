@@ -1,106 +1,230 @@
-use super::{GenerationalIndex, GenerationalIndexValue};
+use super::{
+    sparse_set::{SparseSet, SparseSlots, SparseSlotsMut},
+    GenerationalIndex, GenerationalIndexValue,
+};
 
 #[derive(Serialize, Deserialize, Default, Debug)]
-struct ArrayEntry<T: GenerationalIndexValue> {
+pub(super) struct ArrayEntry<T: GenerationalIndexValue> {
     value: T,
     generation: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+enum Storage<T: GenerationalIndexValue> {
+    /// A slot for every Entity. Lookups are as fast as they get, but iterating walks
+    /// every empty slot too.
+    Dense(Vec<Option<ArrayEntry<T>>>),
+    /// Only the values which exist, packed together. Good for Components which
+    /// only a handful of Entities have, like Player.
+    Sparse(SparseSet<T>),
+}
+
 // An array from GenerationalIndex to some Value T.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct GenerationalIndexArray<T: GenerationalIndexValue>(Vec<Option<ArrayEntry<T>>>);
+pub struct GenerationalIndexArray<T: GenerationalIndexValue> {
+    storage: Storage<T>,
+    /// For every slot, the first generation which hasn't been despawned yet. Anything
+    /// older is a dead handle.
+    #[serde(default)]
+    live_from: Vec<u64>,
+}
 
 impl<T: GenerationalIndexValue> GenerationalIndexArray<T> {
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self {
+            storage: Storage::Dense(Vec::new()),
+            live_from: Vec::new(),
+        }
+    }
+
+    /// Makes an array with sparse-set storage. It has the same API as a normal one,
+    /// but iterating only touches the values which exist. Setting and unsetting are
+    /// a little slower, since we keep the values packed in Entity order.
+    pub fn sparse() -> Self {
+        Self {
+            storage: Storage::Sparse(SparseSet::new()),
+            live_from: Vec::new(),
+        }
     }
 
     /// Returns a mutable Iterator over the internal members of the Vec.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
-        self.0.iter_mut().flat_map(|opt_ent| {
-            let value = &mut opt_ent.as_mut()?.value;
-            if value.is_active() {
-                Some(value)
-            } else {
-                None
-            }
-        })
+        // Only one of these is ever Some.
+        let (dense, sparse) = match &mut self.storage {
+            Storage::Dense(slots) => (Some(slots.iter_mut()), None),
+            Storage::Sparse(set) => (None, Some(set.entries_mut())),
+        };
+
+        dense
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain(sparse.into_iter().flatten())
+            .map(|entry| &mut entry.value)
+            .filter(|value| value.is_active())
     }
 
     /// Returns an immutable Iterator over the internal members of the Vec.
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.0.iter().flat_map(|opt_ent| {
-            let value = &opt_ent.as_ref()?.value;
-            if value.is_active() {
-                Some(value)
-            } else {
-                None
+        // Only one of these is ever Some.
+        let (dense, sparse) = match &self.storage {
+            Storage::Dense(slots) => (Some(slots.iter()), None),
+            Storage::Sparse(set) => (None, Some(set.entries())),
+        };
+
+        dense
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain(sparse.into_iter().flatten())
+            .map(|entry| &entry.value)
+            .filter(|value| value.is_active())
+    }
+
+    /// Returns an Iterator over every slot in the Vec, including empty ones. Empty
+    /// and inactive slots come back as `None`, and the rest come with the generation
+    /// they were set with. This is what Queries are built on.
+    pub fn slots(&self) -> Slots<'_, T> {
+        match &self.storage {
+            Storage::Dense(slots) => Slots(SlotsInner::Dense(slots.iter())),
+            Storage::Sparse(set) => Slots(SlotsInner::Sparse(set.slots())),
+        }
+    }
+
+    /// Returns a mutable Iterator over every slot in the Vec, including empty ones. Empty
+    /// and inactive slots come back as `None`, and the rest come with the generation
+    /// they were set with. This is what Queries are built on.
+    pub fn slots_mut(&mut self) -> SlotsMut<'_, T> {
+        match &mut self.storage {
+            Storage::Dense(slots) => SlotsMut(SlotsMutInner::Dense(slots.iter_mut())),
+            Storage::Sparse(set) => SlotsMut(SlotsMutInner::Sparse(set.slots_mut())),
+        }
+    }
+
+    /// Makes a copy of the whole array, copying each value with `f`. Generations are kept,
+    /// so Entities from the original still point at the same values in the copy. So is
+    /// the storage.
+    pub fn clone_with(&self, f: impl Fn(&T) -> T) -> Self {
+        let clone_entry = |entry: &ArrayEntry<T>| ArrayEntry {
+            value: f(&entry.value),
+            generation: entry.generation,
+        };
+
+        let storage = match &self.storage {
+            Storage::Dense(slots) => {
+                Storage::Dense(slots.iter().map(|slot| slot.as_ref().map(clone_entry)).collect())
             }
-        })
+            Storage::Sparse(set) => Storage::Sparse(set.clone_with(clone_entry)),
+        };
+
+        Self {
+            storage,
+            live_from: self.live_from.clone(),
+        }
     }
 
     /// Set the value for some generational index.  May overwrite past generation
     /// values.
     pub fn set(&mut self, index: &GenerationalIndex, value: T) {
-        self.0[index.index] = Some(ArrayEntry {
+        let entry = ArrayEntry {
             value,
             generation: index.generation,
-        });
+        };
+
+        match &mut self.storage {
+            Storage::Dense(slots) => slots[index.index] = Some(entry),
+            Storage::Sparse(set) => set.set(index.index, entry),
+        }
     }
 
     /// Adds a new component for a new entity to the end of the List. Don't use this
     /// very commonly -- this is for adding new entities, essentially.
     pub fn expand_list(&mut self) {
-        self.0.push(None);
+        match &mut self.storage {
+            Storage::Dense(slots) => slots.push(None),
+            Storage::Sparse(set) => set.expand_list(),
+        }
+        self.live_from.push(0);
     }
 
     /// Unsets the value for some generational index. Returns true if succesfully
     /// unset.
     pub fn unset(&mut self, index: &GenerationalIndex) -> bool {
-        let ret = &self.0[index.index];
-        if let Some(ret) = ret {
-            if ret.generation == index.generation {
-                self.0[index.index] = None;
-                true
-            } else {
-                false
+        if self.entry(index).is_none() {
+            return false;
+        }
+
+        match &mut self.storage {
+            Storage::Dense(slots) => slots[index.index] = None,
+            Storage::Sparse(set) => {
+                set.remove(index.index);
             }
-        } else {
-            false
         }
+        true
     }
 
-    // Checks if the desired index points to a valid location. Merely shorthand
-    // for doing a .is_some() after a get.
+    /// Unsets the value for an Entity which is being despawned, and remembers that its
+    /// generation is dead, so we can catch anyone who holds onto it.
+    pub fn retire(&mut self, index: &GenerationalIndex) -> bool {
+        let unset = self.unset(index);
+        if let Some(live_from) = self.live_from.get_mut(index.index) {
+            *live_from = (*live_from).max(index.generation + 1);
+        }
+        unset
+    }
+
+    // Checks if the desired index points to a valid location. Like a get, this can't
+    // be called with a dead index -- check `EntityAllocator::is_live` first for that.
     pub fn contains(&self, index: &GenerationalIndex) -> bool {
-        self.get(index).is_some()
+        self.debug_assert_alive(index);
+        self.entry(index).is_some()
     }
 
     // Gets an immutable reference to the contained value, if it exists.
     pub fn get(&self, index: &GenerationalIndex) -> Option<&T> {
-        let ret = &self.0[index.index];
-        if let Some(ret) = ret {
-            if ret.generation == index.generation {
-                Some(&ret.value)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+        self.debug_assert_alive(index);
+        self.entry(index).map(|entry| &entry.value)
     }
 
     /// Gets a mutable reference to the contained value, if it exists.
     pub fn get_mut(&mut self, index: &GenerationalIndex) -> Option<&mut T> {
-        let ret = &mut self.0[index.index];
-        if let Some(ret) = ret {
-            if ret.generation == index.generation {
-                Some(&mut ret.value)
-            } else {
-                None
-            }
-        } else {
-            None
+        self.debug_assert_alive(index);
+
+        let entry = match &mut self.storage {
+            Storage::Dense(slots) => slots[index.index].as_mut(),
+            Storage::Sparse(set) => set.get_mut(index.index),
+        };
+
+        entry
+            .filter(|entry| entry.generation == index.generation)
+            .map(|entry| &mut entry.value)
+    }
+
+    /// The entry at this index, if it's from the same generation.
+    fn entry(&self, index: &GenerationalIndex) -> Option<&ArrayEntry<T>> {
+        let entry = match &self.storage {
+            Storage::Dense(slots) => slots[index.index].as_ref(),
+            Storage::Sparse(set) => set.get(index.index),
+        };
+
+        entry.filter(|entry| entry.generation == index.generation)
+    }
+
+    /// If `index` was despawned, or a newer generation has taken over its slot, whoever
+    /// handed it to us held onto an Entity after it was despawned.
+    fn debug_assert_alive(&self, index: &GenerationalIndex) {
+        if cfg!(debug_assertions) {
+            let live_from = self.live_from.get(index.index).copied().unwrap_or_default();
+            let slot_generation = match &self.storage {
+                Storage::Dense(slots) => slots[index.index].as_ref().map(|entry| entry.generation),
+                Storage::Sparse(set) => set.get(index.index).map(|entry| entry.generation),
+            };
+
+            assert!(
+                index.generation >= live_from.max(slot_generation.unwrap_or_default()),
+                "Dereferenced {}, but it's dead. Use `EntityAllocator::is_live` to check a handle which might be.",
+                index
+            );
         }
     }
 }
@@ -110,3 +234,47 @@ impl<T: GenerationalIndexValue> Default for GenerationalIndexArray<T> {
         Self::new()
     }
 }
+
+pub struct Slots<'a, T: GenerationalIndexValue>(SlotsInner<'a, T>);
+
+enum SlotsInner<'a, T: GenerationalIndexValue> {
+    Dense(std::slice::Iter<'a, Option<ArrayEntry<T>>>),
+    Sparse(SparseSlots<'a, T>),
+}
+
+impl<'a, T: GenerationalIndexValue> Iterator for Slots<'a, T> {
+    type Item = Option<(u64, &'a T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = match &mut self.0 {
+            SlotsInner::Dense(slots) => slots.next()?.as_ref(),
+            SlotsInner::Sparse(slots) => slots.next()?,
+        };
+        Some(
+            slot.filter(|entry| entry.value.is_active())
+                .map(|entry| (entry.generation, &entry.value)),
+        )
+    }
+}
+
+pub struct SlotsMut<'a, T: GenerationalIndexValue>(SlotsMutInner<'a, T>);
+
+enum SlotsMutInner<'a, T: GenerationalIndexValue> {
+    Dense(std::slice::IterMut<'a, Option<ArrayEntry<T>>>),
+    Sparse(SparseSlotsMut<'a, T>),
+}
+
+impl<'a, T: GenerationalIndexValue> Iterator for SlotsMut<'a, T> {
+    type Item = Option<(u64, &'a mut T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = match &mut self.0 {
+            SlotsMutInner::Dense(slots) => slots.next()?.as_mut(),
+            SlotsMutInner::Sparse(slots) => slots.next()?,
+        };
+        Some(
+            slot.filter(|entry| entry.value.is_active())
+                .map(|entry| (entry.generation, &mut entry.value)),
+        )
+    }
+}
@@ -0,0 +1,164 @@
+use super::{GenerationalIndexArray, GenerationalIndexValue, Slots, SlotsMut};
+
+/// Anything which can be a part of a Query. This is implemented for `&GenerationalIndexArray`,
+/// `&mut GenerationalIndexArray`, and tuples of those (up to four), so you can write:
+///
+/// `query((&mut component_database.transforms, &component_database.grid_objects))`
+///
+/// Every GenerationalIndexArray in the ComponentDatabase is the same length, so a Query just
+/// walks all of its arrays together, index by index, and yields wherever every array has an
+/// active value from the same generation.
+pub trait QueryTerm {
+    type Item;
+    type Slots: Iterator<Item = Option<(u64, Self::Item)>>;
+
+    fn slots(self) -> Self::Slots;
+}
+
+impl<'a, T: GenerationalIndexValue> QueryTerm for &'a GenerationalIndexArray<T> {
+    type Item = &'a T;
+    type Slots = Slots<'a, T>;
+
+    fn slots(self) -> Self::Slots {
+        GenerationalIndexArray::slots(self)
+    }
+}
+
+impl<'a, T: GenerationalIndexValue> QueryTerm for &'a mut GenerationalIndexArray<T> {
+    type Item = &'a mut T;
+    type Slots = SlotsMut<'a, T>;
+
+    fn slots(self) -> Self::Slots {
+        GenerationalIndexArray::slots_mut(self)
+    }
+}
+
+/// Walks the Slots of every term in a tuple together.
+pub struct TupleSlots<S>(S);
+
+macro_rules! impl_query_tuple {
+    ($(($term:ident, $item:ident, $var:ident)),+) => {
+        impl<$($term: QueryTerm),+> QueryTerm for ($($term,)+) {
+            type Item = ($($term::Item,)+);
+            type Slots = TupleSlots<($($term::Slots,)+)>;
+
+            fn slots(self) -> Self::Slots {
+                let ($($var,)+) = self;
+                TupleSlots(($($var.slots(),)+))
+            }
+        }
+
+        impl<$($term, $item),+> Iterator for TupleSlots<($($term,)+)>
+        where
+            $($term: Iterator<Item = Option<(u64, $item)>>),+
+        {
+            type Item = Option<(u64, ($($item,)+))>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($($var,)+) = &mut self.0;
+                $(let $var = $var.next()?;)+
+
+                match ($($var,)+) {
+                    ($(Some($var),)+) => {
+                        // A slot can still hold a Component from a dead Entity which used to
+                        // live there, so they all have to be from the same generation.
+                        let generations = [$($var.0,)+];
+                        if generations.iter().all(|generation| *generation == generations[0]) {
+                            Some(Some((generations[0], ($($var.1,)+))))
+                        } else {
+                            Some(None)
+                        }
+                    }
+                    _ => Some(None),
+                }
+            }
+        }
+    };
+}
+
+impl_query_tuple!((A, AItem, a));
+impl_query_tuple!((A, AItem, a), (B, BItem, b));
+impl_query_tuple!((A, AItem, a), (B, BItem, b), (C, CItem, c));
+impl_query_tuple!((A, AItem, a), (B, BItem, b), (C, CItem, c), (D, DItem, d));
+
+/// Starts a Query over the given terms. See `QueryTerm` for what can go in here.
+pub fn query<Q: QueryTerm>(terms: Q) -> Query<Q::Slots> {
+    Query(terms.slots())
+}
+
+/// An Iterator over every entity which has all the components in a Query, and passes
+/// all of its `with` and `without` filters. Inactive components count as missing.
+pub struct Query<I>(I);
+
+impl<I, X> Query<I>
+where
+    I: Iterator<Item = Option<(u64, X)>>,
+{
+    /// Only yields entities which also have an active value in `list`, from their generation.
+    pub fn with<U: GenerationalIndexValue>(
+        self,
+        list: &GenerationalIndexArray<U>,
+    ) -> Query<QueryFilter<I, Slots<'_, U>>> {
+        Query(QueryFilter {
+            slots: self.0,
+            filter: list.slots(),
+            keep_if_present: true,
+        })
+    }
+
+    /// Only yields entities which do *not* have an active value in `list`.
+    pub fn without<U: GenerationalIndexValue>(
+        self,
+        list: &GenerationalIndexArray<U>,
+    ) -> Query<QueryFilter<I, Slots<'_, U>>> {
+        Query(QueryFilter {
+            slots: self.0,
+            filter: list.slots(),
+            keep_if_present: false,
+        })
+    }
+}
+
+impl<I, X> Iterator for Query<I>
+where
+    I: Iterator<Item = Option<(u64, X)>>,
+{
+    type Item = X;
+
+    fn next(&mut self) -> Option<X> {
+        loop {
+            if let Some((_, item)) = self.0.next()? {
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// The Slots of a Query after a `with` or `without`.
+pub struct QueryFilter<I, F> {
+    slots: I,
+    filter: F,
+    keep_if_present: bool,
+}
+
+impl<I, F, X, Y> Iterator for QueryFilter<I, F>
+where
+    I: Iterator<Item = Option<(u64, X)>>,
+    F: Iterator<Item = Option<(u64, Y)>>,
+{
+    type Item = Option<(u64, X)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.slots.next()?;
+        let present = match (&item, self.filter.next()?) {
+            (Some((generation, _)), Some((filter_generation, _))) => *generation == filter_generation,
+            _ => false,
+        };
+
+        if present == self.keep_if_present {
+            Some(item)
+        } else {
+            Some(None)
+        }
+    }
+}
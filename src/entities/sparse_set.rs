@@ -0,0 +1,152 @@
+use super::generational_index_array::ArrayEntry;
+use super::GenerationalIndexValue;
+use std::iter::{Peekable, Zip};
+
+/// Storage for a GenerationalIndexArray where most Entities don't have a value.
+/// The values are packed together in `dense`, which we keep sorted by Entity index,
+/// so we iterate them in the same order a dense array would.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct SparseSet<T: GenerationalIndexValue> {
+    /// For every Entity index, where its value is in `dense`.
+    sparse: Vec<Option<usize>>,
+    dense: Vec<ArrayEntry<T>>,
+    /// The Entity index of every value in `dense`.
+    indices: Vec<usize>,
+}
+
+impl<T: GenerationalIndexValue> SparseSet<T> {
+    pub fn new() -> Self {
+        Self {
+            sparse: Vec::new(),
+            dense: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    pub fn expand_list(&mut self) {
+        self.sparse.push(None);
+    }
+
+    pub fn entries(&self) -> std::slice::Iter<'_, ArrayEntry<T>> {
+        self.dense.iter()
+    }
+
+    pub fn entries_mut(&mut self) -> std::slice::IterMut<'_, ArrayEntry<T>> {
+        self.dense.iter_mut()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ArrayEntry<T>> {
+        self.sparse[index].map(|dense_index| &self.dense[dense_index])
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut ArrayEntry<T>> {
+        match self.sparse[index] {
+            Some(dense_index) => Some(&mut self.dense[dense_index]),
+            None => None,
+        }
+    }
+
+    pub fn set(&mut self, index: usize, entry: ArrayEntry<T>) {
+        if let Some(dense_index) = self.sparse[index] {
+            self.dense[dense_index] = entry;
+            return;
+        }
+
+        let dense_index = match self.indices.binary_search(&index) {
+            Ok(dense_index) | Err(dense_index) => dense_index,
+        };
+        self.dense.insert(dense_index, entry);
+        self.indices.insert(dense_index, index);
+        self.reindex_from(dense_index);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<ArrayEntry<T>> {
+        let dense_index = self.sparse[index].take()?;
+
+        let entry = self.dense.remove(dense_index);
+        self.indices.remove(dense_index);
+        self.reindex_from(dense_index);
+
+        Some(entry)
+    }
+
+    /// Every slot in Entity order, with `None` for Entities without a value.
+    pub fn slots(&self) -> SparseSlots<'_, T> {
+        SparseSlots {
+            position: 0,
+            len: self.sparse.len(),
+            entries: self.indices.iter().zip(self.dense.iter()).peekable(),
+        }
+    }
+
+    pub fn slots_mut(&mut self) -> SparseSlotsMut<'_, T> {
+        SparseSlotsMut {
+            position: 0,
+            len: self.sparse.len(),
+            entries: self.indices.iter().zip(self.dense.iter_mut()).peekable(),
+        }
+    }
+
+    pub fn clone_with(&self, f: impl Fn(&ArrayEntry<T>) -> ArrayEntry<T>) -> Self {
+        Self {
+            sparse: self.sparse.clone(),
+            dense: self.dense.iter().map(f).collect(),
+            indices: self.indices.clone(),
+        }
+    }
+
+    /// Everything after `dense_index` has shifted, so their Entities need to be told.
+    fn reindex_from(&mut self, dense_index: usize) {
+        for (i, index) in self.indices.iter().enumerate().skip(dense_index) {
+            self.sparse[*index] = Some(i);
+        }
+    }
+}
+
+// Since `dense` is sorted by Entity index, we can count up through the Entities, taking
+// the next value every time we reach the Entity it belongs to.
+pub(super) struct SparseSlots<'a, T: GenerationalIndexValue> {
+    position: usize,
+    len: usize,
+    entries: Peekable<Zip<std::slice::Iter<'a, usize>, std::slice::Iter<'a, ArrayEntry<T>>>>,
+}
+
+impl<'a, T: GenerationalIndexValue> Iterator for SparseSlots<'a, T> {
+    type Item = Option<&'a ArrayEntry<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.len {
+            return None;
+        }
+
+        let position = self.position;
+        self.position += 1;
+        match self.entries.peek() {
+            Some((index, _)) if **index == position => Some(self.entries.next().map(|(_, entry)| entry)),
+            _ => Some(None),
+        }
+    }
+}
+
+pub(super) struct SparseSlotsMut<'a, T: GenerationalIndexValue> {
+    position: usize,
+    len: usize,
+    entries: Peekable<Zip<std::slice::Iter<'a, usize>, std::slice::IterMut<'a, ArrayEntry<T>>>>,
+}
+
+impl<'a, T: GenerationalIndexValue> Iterator for SparseSlotsMut<'a, T> {
+    type Item = Option<&'a mut ArrayEntry<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.len {
+            return None;
+        }
+
+        let position = self.position;
+        self.position += 1;
+        match self.entries.peek() {
+            Some((index, _)) if **index == position => Some(self.entries.next().map(|(_, entry)| entry)),
+            _ => Some(None),
+        }
+    }
+}
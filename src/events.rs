@@ -0,0 +1,84 @@
+use super::{cardinals::CardinalPrime, Entity, Scene};
+
+/// A single kind of Event. Systems `send` into it, and any later system in the
+/// same frame can read it with `iter`. A frame can run a few ticks, so readers
+/// shouldn't mind seeing an Event more than once.
+#[derive(Debug)]
+pub struct EventChannel<T> {
+    events: Vec<T>,
+}
+
+impl<T> EventChannel<T> {
+    pub fn send(&mut self, event: T) {
+        self.events.push(event);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.events.iter()
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+impl<T> Default for EventChannel<T> {
+    fn default() -> Self {
+        Self { events: Vec::new() }
+    }
+}
+
+/// Every Event Channel on the Ecs. These are cleared at the end of every frame, after PreRender.
+#[derive(Debug, Default)]
+pub struct Events {
+    pub entity_moved: EventChannel<EntityMoved>,
+    pub box_pushed: EventChannel<BoxPushed>,
+    pub level_solved: EventChannel<LevelSolved>,
+    pub player_switched: EventChannel<PlayerSwitched>,
+    pub scene_requested: EventChannel<SceneRequested>,
+}
+
+impl Events {
+    pub fn clear(&mut self) {
+        self.entity_moved.clear();
+        self.box_pushed.clear();
+        self.level_solved.clear();
+        self.player_switched.clear();
+        self.scene_requested.clear();
+    }
+}
+
+/// An Entity moved from one Grid position to another.
+#[derive(Debug, Clone, Copy)]
+pub struct EntityMoved {
+    pub entity: Entity,
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+}
+
+/// A Pushable was pushed by `pusher`.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxPushed {
+    pub entity: Entity,
+    pub pusher: Entity,
+    pub direction: CardinalPrime,
+}
+
+/// A Player reached a Flag.
+#[derive(Debug, Clone, Copy)]
+pub struct LevelSolved {
+    pub player: Entity,
+    pub flag: Entity,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerSwitched {
+    pub from: Entity,
+    pub to: Entity,
+}
+
+/// Something wants us to load a new Scene at the end of the frame.
+#[derive(Debug, Clone)]
+pub struct SceneRequested {
+    pub scene: Scene,
+}
@@ -1,4 +1,4 @@
-use super::{sprite_resources::SpriteName, Camera, Color, DrawOrder, Vec2, Vec2Int};
+use super::{sprite_resources::SpriteName, Affine2, Camera, Color, DrawOrder, Vec2, Vec2Int};
 
 macro_rules! manual_drop {
     ($this_val:expr) => {
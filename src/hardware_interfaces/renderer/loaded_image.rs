@@ -39,116 +39,171 @@ impl<B: Backend> LoadedImage<B> {
         filter: gfx_hal::image::Filter,
     ) -> Result<Self, Error> {
         unsafe {
-            // Make the Image Object!
-            let mut image_object = device
-                .create_image(
-                    gfx_hal::image::Kind::D2(width as u32, height as u32, 1, 1),
-                    1,
-                    Format::Rgba8Srgb,
-                    gfx_hal::image::Tiling::Optimal,
-                    Usage::TRANSFER_DST | Usage::SAMPLED,
-                    gfx_hal::image::ViewCapabilities::empty(),
-                )
-                .map_err(|e| LoadedImageError::CreateImage(e))?;
-
-            //  Allocate the memory and bind it
-            let requirements = device.get_image_requirements(&image_object);
-            let memory_type_id = adapter
-                .physical_device
-                .memory_properties()
-                .memory_types
-                .iter()
-                .enumerate()
-                .find(|&(id, memory_type)| {
-                    requirements.type_mask & (1 << id) != 0
-                        && memory_type.properties.contains(Properties::DEVICE_LOCAL)
-                })
-                .map(|(id, _)| MemoryTypeId(id))
-                .ok_or(BufferError::MemoryId)?;
-
-            let memory = device
-                .allocate_memory(memory_type_id, requirements.size)
-                .map_err(|e| BufferError::Allocate(e))?;
-
-            device
-                .bind_image_memory(&memory, 0, &mut image_object)
-                .map_err(|e| BufferError::Bind(e))?;
-
-            //  Create image view and sampler
-            let image_view = device
-                .create_image_view(
-                    &image_object,
-                    gfx_hal::image::ViewKind::D2,
-                    Format::Rgba8Srgb,
-                    gfx_hal::format::Swizzle::NO,
-                    SubresourceRange {
-                        aspects: Aspects::COLOR,
-                        levels: 0..1,
-                        layers: 0..1,
-                    },
-                )
-                .map_err(|e| LoadedImageError::ImageView(e))?;
-
-            let sampler = device
-                .create_sampler(&gfx_hal::image::SamplerDesc::new(
-                    filter,
-                    gfx_hal::image::WrapMode::Clamp,
-                ))
-                .map_err(|e| LoadedImageError::Sampler(e))?;
+            let (image_object, requirements, memory, image_view, sampler) =
+                LoadedImage::create_image_object(adapter, device, width, height, filter)?;
 
             let descriptor_set = pipeline_bundle.allocate_descriptor_set()?;
 
             //  Create our image and do some final tweaking to it!
-            let texture = {
-                let mut texture = Self {
-                    image: manual_new!(image_object),
-                    requirements,
-                    memory: manual_new!(memory),
-                    image_view: manual_new!(image_view),
-                    sampler: manual_new!(sampler),
-                    descriptor_set: manual_new!(descriptor_set),
-                    phantom: PhantomData,
-                };
-
-                // Create a staging bundle of our passed in Data
-                // and upload it into the image object
-                texture.edit_image(
-                    width as u32,
-                    height as u32,
-                    Vec2Int::new(0, 0),
-                    img,
-                    adapter,
-                    device,
-                    command_pool,
-                    command_queue,
-                )?;
-
-                // Write that fucker: Write the descriptors into the descriptor set
-                device.write_descriptor_sets(vec![
-                    DescriptorSetWrite {
-                        set: texture.descriptor_set.deref(),
-                        binding: 0,
-                        array_offset: 0,
-                        descriptors: Some(Descriptor::Image(
-                            texture.image_view.deref(),
-                            Layout::ShaderReadOnlyOptimal,
-                        )),
-                    },
-                    DescriptorSetWrite {
-                        set: texture.descriptor_set.deref(),
-                        binding: 1,
-                        array_offset: 0,
-                        descriptors: Some(Descriptor::Sampler(texture.sampler.deref())),
-                    },
-                ]);
-
-                texture
+            let mut texture = Self {
+                image: manual_new!(image_object),
+                requirements,
+                memory: manual_new!(memory),
+                image_view: manual_new!(image_view),
+                sampler: manual_new!(sampler),
+                descriptor_set: manual_new!(descriptor_set),
+                phantom: PhantomData,
             };
 
+            // Create a staging bundle of our passed in Data
+            // and upload it into the image object
+            texture.edit_image(
+                width as u32,
+                height as u32,
+                Vec2Int::new(0, 0),
+                img,
+                adapter,
+                device,
+                command_pool,
+                command_queue,
+            )?;
+            texture.write_descriptor_set(device);
+
             Ok(texture)
         }
     }
 
+    /// Swaps in a new image (of any size) but keeps our descriptor set, so
+    /// anything pointing at this texture sees the new one. The old image is
+    /// destroyed right away, so the device can't still be using it!
+    pub unsafe fn replace_image(
+        &mut self,
+        adapter: &Adapter<B>,
+        device: &B::Device,
+        command_pool: &mut B::CommandPool,
+        command_queue: &mut B::CommandQueue,
+        img: &[u8],
+        width: usize,
+        height: usize,
+        filter: gfx_hal::image::Filter,
+    ) -> Result<(), Error> {
+        let (image_object, requirements, memory, image_view, sampler) =
+            LoadedImage::create_image_object(adapter, device, width, height, filter)?;
+
+        let old_image = std::mem::replace(&mut self.image, manual_new!(image_object));
+        let old_memory = std::mem::replace(&mut self.memory, manual_new!(memory));
+        let old_image_view = std::mem::replace(&mut self.image_view, manual_new!(image_view));
+        let old_sampler = std::mem::replace(&mut self.sampler, manual_new!(sampler));
+        self.requirements = requirements;
+
+        device.destroy_sampler(ManuallyDrop::into_inner(old_sampler));
+        device.destroy_image_view(ManuallyDrop::into_inner(old_image_view));
+        device.destroy_image(ManuallyDrop::into_inner(old_image));
+        device.free_memory(ManuallyDrop::into_inner(old_memory));
+
+        self.edit_image(
+            width as u32,
+            height as u32,
+            Vec2Int::new(0, 0),
+            img,
+            adapter,
+            device,
+            command_pool,
+            command_queue,
+        )?;
+        self.write_descriptor_set(device);
+
+        Ok(())
+    }
+
+    unsafe fn create_image_object(
+        adapter: &Adapter<B>,
+        device: &B::Device,
+        width: usize,
+        height: usize,
+        filter: gfx_hal::image::Filter,
+    ) -> Result<(B::Image, Requirements, B::Memory, B::ImageView, B::Sampler), Error> {
+        // Make the Image Object!
+        let mut image_object = device
+            .create_image(
+                gfx_hal::image::Kind::D2(width as u32, height as u32, 1, 1),
+                1,
+                Format::Rgba8Srgb,
+                gfx_hal::image::Tiling::Optimal,
+                Usage::TRANSFER_DST | Usage::SAMPLED,
+                gfx_hal::image::ViewCapabilities::empty(),
+            )
+            .map_err(|e| LoadedImageError::CreateImage(e))?;
+
+        //  Allocate the memory and bind it
+        let requirements = device.get_image_requirements(&image_object);
+        let memory_type_id = adapter
+            .physical_device
+            .memory_properties()
+            .memory_types
+            .iter()
+            .enumerate()
+            .find(|&(id, memory_type)| {
+                requirements.type_mask & (1 << id) != 0
+                    && memory_type.properties.contains(Properties::DEVICE_LOCAL)
+            })
+            .map(|(id, _)| MemoryTypeId(id))
+            .ok_or(BufferError::MemoryId)?;
+
+        let memory = device
+            .allocate_memory(memory_type_id, requirements.size)
+            .map_err(|e| BufferError::Allocate(e))?;
+
+        device
+            .bind_image_memory(&memory, 0, &mut image_object)
+            .map_err(|e| BufferError::Bind(e))?;
+
+        //  Create image view and sampler
+        let image_view = device
+            .create_image_view(
+                &image_object,
+                gfx_hal::image::ViewKind::D2,
+                Format::Rgba8Srgb,
+                gfx_hal::format::Swizzle::NO,
+                SubresourceRange {
+                    aspects: Aspects::COLOR,
+                    levels: 0..1,
+                    layers: 0..1,
+                },
+            )
+            .map_err(|e| LoadedImageError::ImageView(e))?;
+
+        let sampler = device
+            .create_sampler(&gfx_hal::image::SamplerDesc::new(
+                filter,
+                gfx_hal::image::WrapMode::Clamp,
+            ))
+            .map_err(|e| LoadedImageError::Sampler(e))?;
+
+        Ok((image_object, requirements, memory, image_view, sampler))
+    }
+
+    unsafe fn write_descriptor_set(&self, device: &B::Device) {
+        // Write that fucker: Write the descriptors into the descriptor set
+        device.write_descriptor_sets(vec![
+            DescriptorSetWrite {
+                set: self.descriptor_set.deref(),
+                binding: 0,
+                array_offset: 0,
+                descriptors: Some(Descriptor::Image(
+                    self.image_view.deref(),
+                    Layout::ShaderReadOnlyOptimal,
+                )),
+            },
+            DescriptorSetWrite {
+                set: self.descriptor_set.deref(),
+                binding: 1,
+                array_offset: 0,
+                descriptors: Some(Descriptor::Sampler(self.sampler.deref())),
+            },
+        ]);
+    }
+
     pub fn edit_image(
         &mut self,
         width: u32,
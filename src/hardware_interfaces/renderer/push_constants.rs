@@ -5,13 +5,16 @@ use super::{Camera, Color, StandardQuad, StandardTexture, Vec2};
 #[derive(Debug, Default, Clone)]
 #[repr(C)]
 pub struct StandardPushConstants {
+    pub color: Color,
     pub camera_position: Vec2,
     pub camera_native_resolution: Vec2,
-    pub entity_position: Vec2,
+    pub entity_translation: Vec2,
+    pub entity_x_axis: Vec2,
+    pub entity_y_axis: Vec2,
+    pub quad_position: Vec2,
     pub image_size: Vec2,
     pub norm_image_coord: Vec2,
     pub norm_image_size: Vec2,
-    pub color: Color,
 }
 
 impl StandardPushConstants {
@@ -24,9 +27,7 @@ impl StandardPushConstants {
     }
 
     pub fn update(&mut self, standard_quad: &StandardQuad, texture_info: &StandardTexture) {
-        self.color = standard_quad.color;
-        self.entity_position = standard_quad.pos;
-        self.image_size = standard_quad.image_size;
+        standard_quad.update_with(self);
         self.norm_image_coord = texture_info.norm_image_coordinate;
         self.norm_image_size = texture_info.norm_image_size;
     }
@@ -1,9 +1,12 @@
-use super::{Color, DrawOrder, StandardPushConstants, Vec2};
+use super::{Affine2, Color, DrawOrder, StandardPushConstants, Vec2};
 use std::cmp::Ordering;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct StandardQuad {
     pub color: Color,
+    /// The world transform of whatever we're drawing.
+    pub transform: Affine2,
+    /// The corner of the quad, before `transform` is applied.
     pub pos: Vec2,
     pub draw_order: DrawOrder,
     pub image_size: Vec2,
@@ -13,9 +16,17 @@ pub struct StandardQuad {
 impl StandardQuad {
     pub fn update_with(&self, spc: &mut StandardPushConstants) {
         spc.color = self.color;
-        spc.entity_position = self.pos;
+        spc.entity_translation = self.transform.translation;
+        spc.entity_x_axis = self.transform.x_axis;
+        spc.entity_y_axis = self.transform.y_axis;
+        spc.quad_position = self.pos;
         spc.image_size = self.image_size;
     }
+
+    /// Where our corner ends up in the world.
+    pub fn world_position(&self) -> Vec2 {
+        self.transform.transform_point(self.pos)
+    }
 }
 
 impl Eq for StandardQuad {}
@@ -28,9 +39,13 @@ impl PartialOrd for StandardQuad {
 
 impl Ord for StandardQuad {
     fn cmp(&self, rhs: &StandardQuad) -> Ordering {
-        self.draw_order
-            .cmp(&rhs.draw_order)
-            .then_with(|| self.pos.y.partial_cmp(&rhs.pos.y).unwrap().reverse())
+        self.draw_order.cmp(&rhs.draw_order).then_with(|| {
+            self.world_position()
+                .y
+                .partial_cmp(&rhs.world_position().y)
+                .unwrap()
+                .reverse()
+        })
     }
 }
 
@@ -54,5 +69,5 @@ impl From<StandardTexture> for TextureDescription {
 }
 
 pub trait StandardQuadFactory {
-    fn to_standard_quad(&self, pos: Vec2) -> StandardQuad;
+    fn to_standard_quad(&self, transform: &Affine2) -> StandardQuad;
 }
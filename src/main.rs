@@ -11,39 +11,67 @@ extern crate anyhow;
 
 mod action_map;
 mod clockwork;
+mod command_buffer;
+#[macro_use]
 mod components;
+#[macro_use]
 mod components_singleton;
 mod ecs;
 mod entities;
+mod events;
 mod hardware_interfaces;
 mod physics;
 mod resources;
 mod scene;
 mod scene_graph;
+mod scene_stack;
 mod serialization;
+mod stale_handles;
 mod systems;
+mod tag_index;
 mod tick_structs;
 mod utilities;
+mod world_snapshot;
 
 pub use action_map::ActionMap;
 pub use clockwork::*;
+pub use command_buffer::CommandBuffer;
 pub use components::*;
 pub use components_singleton::*;
 pub use ecs::*;
 pub use entities::*;
+pub use events::*;
 pub use hardware_interfaces::*;
 pub use physics::*;
 pub use resources::*;
 pub use scene::*;
 pub use scene_graph::*;
+pub use scene_stack::*;
 pub use serialization::*;
+pub use stale_handles::*;
 pub use systems::*;
+pub use tag_index::TagIndex;
 pub use tick_structs::*;
 pub use utilities::*;
+pub use world_snapshot::*;
 
 fn main() {
     pretty_env_logger::init();
 
+    if std::env::args().any(|arg| arg == "--migrate") {
+        if let Err(e) = migrations::migrate_all() {
+            error!("Error Migrating: {}", e);
+        }
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--cook") {
+        if let Err(e) = serialization_util::cooking::cook_all() {
+            error!("Error Cooking: {}", e);
+        }
+        return;
+    }
+
     let mut clockwork = match clockwork::Clockwork::new() {
         Ok(clockwork) => clockwork,
         Err(e) => {
@@ -7,6 +7,7 @@ use super::{
     Prefab, PrefabMap, RendererComponent, SoundResource, Vec2,
 };
 use anyhow::Error;
+use clockwork_build_shared::sprite_packing;
 use glyph_brush::GlyphBrushBuilder;
 use std::collections::HashMap;
 use std::fs;
@@ -21,6 +22,7 @@ pub struct ResourcesDatabase {
     pub fonts: HashMap<FontName, FontData>,
     pub config: Config,
     prefabs: PrefabMap,
+    sprite_page: Option<usize>,
 }
 
 impl ResourcesDatabase {
@@ -31,6 +33,7 @@ impl ResourcesDatabase {
             sounds: HashMap::new(),
             fonts: HashMap::new(),
             prefabs: HashMap::new(),
+            sprite_page: None,
             config: serialization_util::game_config::load_config().unwrap_or_default(),
         }
     }
@@ -43,6 +46,7 @@ impl ResourcesDatabase {
         let sprite_resource: Vec<u8> = ResourcesDatabase::load_spritesheets()?;
         let image = image::load_from_memory_with_format(&sprite_resource, image::ImageFormat::PNG)?.to_rgba();
         let handle = renderer_system::register_texture(renderer, &image)?;
+        self.sprite_page = Some(handle);
 
         // LOAD PREFABS
         info!("....................Loading Prefabs");
@@ -118,6 +122,66 @@ impl ResourcesDatabase {
         Ok(())
     }
 
+    /// Packs every PNG in `assets/textures` again, uploads the new sheet over the old page,
+    /// and moves each Sprite onto it in place, keeping any edits to its in-game data. Only for
+    /// debug builds, since release builds don't ship the PNGs.
+    pub fn repack_sprites(&mut self, renderer: &mut RendererComponent) -> Result<(), Error> {
+        sprite_packing::parse_sprites::pack_all_sprites().map_err(|e| format_err!("{}", e))?;
+
+        let sprite_resource: Vec<u8> = ResourcesDatabase::load_spritesheets()?;
+        let image = image::load_from_memory_with_format(&sprite_resource, image::ImageFormat::PNG)?.to_rgba();
+        let handle = match self.sprite_page {
+            Some(page) => {
+                renderer_system::replace_texture(renderer, page, &image)?;
+                page
+            }
+            None => renderer_system::register_texture(renderer, &image)?,
+        };
+        self.sprite_page = Some(handle);
+
+        let sprite_sheet = serialization_util::sprites::load_spritesheets()?;
+        let texture_dimensions = Vec2::new(
+            sprite_sheet.texture_width as f32,
+            sprite_sheet.texture_height as f32,
+        );
+
+        for sprite_sheet_data in sprite_sheet.sprites.into_iter() {
+            let sprite_name = match SpriteName::from_str(&sprite_sheet_data.name) {
+                Ok(sprite_name) => sprite_name,
+                Err(_) => {
+                    error!(
+                        "A file named {} was in SpriteSheet, but we have no Enum to refer to it!",
+                        sprite_sheet_data.name
+                    );
+                    continue;
+                }
+            };
+
+            let texture_information = TextureInformation {
+                page: handle,
+                dimensions: texture_dimensions,
+            };
+
+            match self.sprites.get_mut(&sprite_name) {
+                Some(sprite_data) => sprite_data.repack(sprite_sheet_data, texture_information),
+                None => {
+                    let sprite_metadata = SpriteInGameData::create_default(&sprite_sheet_data, sprite_name);
+                    self.sprites.insert(
+                        sprite_name,
+                        SpriteData::from_sprite_resource(
+                            sprite_sheet_data,
+                            sprite_metadata,
+                            sprite_name,
+                            texture_information,
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn initialize_tilesets(&mut self) -> Result<(), Error> {
         let tilesets: Vec<TileSet> = serialization_util::tilesets::load_all_tilesets()?;
         for ts in tilesets {
@@ -70,4 +70,21 @@ impl SpriteData {
             facing_vertical: sprite_meta_data.facing_vertical,
         }
     }
+
+    /// Moves us onto a freshly packed sheet, keeping our in-game data. Any frames the
+    /// sheet added get the default duration.
+    pub fn repack(&mut self, sprite_resource: SpriteResource, texture_information: TextureInformation) {
+        let mut sprite_meta_data = SpriteInGameData::from(self.clone());
+        sprite_meta_data.frame_durations.resize(
+            sprite_resource.frames.len(),
+            SpriteInGameData::DEFAULT_FRAME_DURATION,
+        );
+
+        *self = SpriteData::from_sprite_resource(
+            sprite_resource,
+            sprite_meta_data,
+            self.sprite_name,
+            texture_information,
+        );
+    }
 }
@@ -29,10 +29,12 @@ impl From<SpriteData> for SpriteInGameData {
 }
 
 impl SpriteInGameData {
+    pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
     pub fn create_default(o: &SpriteResource, name: SpriteName) -> Self {
         let mut frame_durations = Vec::new();
         for _ in &o.frames {
-            frame_durations.push(0.1);
+            frame_durations.push(Self::DEFAULT_FRAME_DURATION);
         }
 
         Self {
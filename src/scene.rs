@@ -49,6 +49,10 @@ impl Scene {
         self.mode = SceneMode::Paused;
     }
 
+    pub fn stop_scene(&mut self) {
+        self.mode = SceneMode::Draft;
+    }
+
     pub fn entity_path(&self) -> String {
         if self.is_prefab {
             format!("{}/{}.prefab", PREFAB_DIRECTORY, &self.name)
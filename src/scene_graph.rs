@@ -1,62 +1,238 @@
 use super::{
-    imgui_component_utils::NameInspectorParameters, ComponentData, ComponentDatabase, ComponentList, Entity,
-    GraphNode, Name, PrefabMarker, ResourcesDatabase, SerializationMarker, SerializedEntity,
-    SingletonDatabase, Transform, Vec2,
+    imgui_component_utils::NameInspectorParameters, Affine2, Component, ComponentData, ComponentDatabase,
+    ComponentList, Entity, GraphNode, Name, PrefabMarker, ResourcesDatabase, Scene, SerializedEntity,
+    SingletonDatabase, Transform,
 };
-use lazy_static::lazy_static;
-use std::sync::Mutex;
 
-lazy_static! {
-    pub static ref ROOT_NODES: Mutex<GraphNode> = Mutex::new(GraphNode {
-        children: Some(vec![])
-    });
+/// The Root of the Scene Graph. Every Ecs owns its own. Transforms keep raw pointers
+/// to the Root node, so it's boxed to stay put when the Ecs moves.
+#[derive(Clone)]
+pub struct SceneGraph {
+    root: Box<GraphNode>,
 }
 
-pub fn add_to_scene_graph<'a>(
-    transform_c: impl Into<ComponentData<'a, Transform>>,
-    serializations: &ComponentList<SerializationMarker>,
-) {
-    ROOT_NODES
-        .lock()
-        .unwrap()
-        .add_child_directly(None, transform_c.into(), serializations);
+impl Default for SceneGraph {
+    fn default() -> Self {
+        Self {
+            root: Box::new(GraphNode {
+                children: Some(vec![]),
+            }),
+        }
+    }
 }
 
-pub fn clear_root() {
-    if let Some(children) = &mut ROOT_NODES.lock().unwrap().children {
-        children.clear();
+impl SceneGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_to_scene_graph<'a>(
+        &mut self,
+        transform_c: impl Into<ComponentData<'a, Transform>>,
+        serializations: &ComponentList<SerializationMarker>,
+    ) {
+        self.root
+            .add_child_directly(None, transform_c.into(), serializations);
+    }
+
+    /// The Entities directly beneath the Root, in the order they were added.
+    pub fn root_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.root
+            .children
+            .iter()
+            .flatten()
+            .filter_map(|child| child.target)
+    }
+
+    /// Finds an Entity by the Names of it and its ancestors, separated by `/`, like
+    /// `"Level/Walls/NorthWall"`. If two siblings share a Name, we take the first.
+    pub fn find_by_path(
+        &self,
+        path: &str,
+        names: &ComponentList<Name>,
+        nodes: &ComponentList<GraphNode>,
+    ) -> Option<Entity> {
+        let is_named = |entity: &Entity, name: &str| {
+            names
+                .get(entity)
+                .map_or(false, |name_c| name_c.inner().name == name)
+        };
+
+        let mut segments = path.split('/').filter(|segment| segment.is_empty() == false);
+        let first = segments.next()?;
+        let mut current = self.root_entities().find(|entity| is_named(entity, first))?;
+
+        for segment in segments {
+            current = nodes
+                .get(&current)?
+                .inner()
+                .children
+                .iter()
+                .flatten()
+                .filter_map(|child| child.target)
+                .find(|child| is_named(child, segment))?;
+        }
+
+        Some(current)
+    }
+
+    /// Transforms keep raw pointers to their parent's GraphNode. After the Root and the
+    /// GraphNodes have moved, like when a WorldSnapshot is restored, this points every
+    /// Transform at the new ones.
+    pub fn relink(
+        &mut self,
+        entities: &[Entity],
+        transforms: &mut ComponentList<Transform>,
+        nodes: &mut ComponentList<GraphNode>,
+    ) {
+        let root: *mut GraphNode = &mut *self.root;
+
+        for entity in entities {
+            let transform_c = match transforms.get_mut(entity) {
+                Some(transform_c) => transform_c,
+                None => continue,
+            };
+
+            if transform_c.inner().in_scene_graph() == false {
+                continue;
+            }
+
+            let graph_node: *mut GraphNode = match transform_c.inner().parent_entity() {
+                Some(parent) => match nodes.get_mut(&parent) {
+                    Some(node) => node.inner_mut(),
+                    None => {
+                        error!(
+                            "{}'s parent {} has no GraphNode! Taking it out of the Scene Graph.",
+                            entity, parent
+                        );
+                        std::ptr::null_mut()
+                    }
+                },
+                None => root,
+            };
+
+            transform_c.inner_mut().relink_parent(graph_node);
+        }
+    }
+
+    /// Recomputes the world transform of every dirty Transform, and of everything beneath
+    /// it. Subtrees where nothing moved are left alone.
+    pub fn propagate_transforms(
+        &self,
+        transforms: &mut ComponentList<Transform>,
+        nodes: &ComponentList<GraphNode>,
+    ) {
+        // If an ancestor is dirty too, we'll get recomputed when it does.
+        let dirty_roots: Vec<Entity> = transforms
+            .iter()
+            .filter(|transform_c| transform_c.inner().is_dirty() && transform_c.inner().in_scene_graph())
+            .map(|transform_c| transform_c.entity_id())
+            .filter(|entity| has_dirty_ancestor(entity, transforms) == false)
+            .collect();
+
+        for entity in dirty_roots {
+            let parent_transform = transforms
+                .get(&entity)
+                .and_then(|transform_c| transform_c.inner().parent_entity())
+                .and_then(|parent| transforms.get(&parent))
+                .map(|parent_c| parent_c.inner().world_transform())
+                .unwrap_or(Affine2::IDENTITY);
+
+            walk_node(&entity, transforms, nodes, &parent_transform);
+        }
+    }
+
+    pub fn walk_graph_inspect(
+        &self,
+        scene: &Scene,
+        component_database: &mut ComponentDatabase,
+        singleton_database: &mut SingletonDatabase,
+        resources: &ResourcesDatabase,
+        f: GraphInspectorLambda<'_>,
+    ) {
+        for target in self.root_entities() {
+            walk_node_inspect(
+                &target,
+                scene,
+                component_database,
+                singleton_database,
+                resources,
+                0,
+                f,
+            );
+        }
+    }
+}
+
+pub fn remove_from_scene_graph(transform_c: &mut Component<Transform>) {
+    let id = transform_c.entity_id();
+    if transform_c.inner().in_scene_graph() {
+        transform_c.inner_mut().remove_from_scene_graph(id);
+    }
+}
+
+/// Clears every Transform's `changed` flag. Clockwork calls this once a frame, right after
+/// the editor, since the editor is the last to read them.
+pub fn clear_transform_changes(transforms: &mut ComponentList<Transform>) {
+    for transform_c in transforms.iter_mut() {
+        transform_c.inner_mut().clear_changed();
     }
 }
 
-pub fn walk_graph(transforms: &mut ComponentList<Transform>, nodes: &ComponentList<GraphNode>) {
-    let root_nodes = ROOT_NODES.lock().unwrap();
+/// Returns the Entity and every Entity beneath it in the Scene Graph, parents first.
+pub fn collect_descendants(entity: &Entity, nodes: &ComponentList<GraphNode>) -> Vec<Entity> {
+    let mut descendants = vec![*entity];
+    let mut i = 0;
+
+    while i < descendants.len() {
+        if let Some(node) = nodes.get(&descendants[i]) {
+            if let Some(children) = &node.inner().children {
+                descendants.extend(children.iter().filter_map(|child| child.target));
+            }
+        }
+        i += 1;
+    }
+
+    descendants
+}
+
+fn has_dirty_ancestor(entity: &Entity, transforms: &ComponentList<Transform>) -> bool {
+    let mut parent = transforms
+        .get(entity)
+        .and_then(|transform_c| transform_c.inner().parent_entity());
 
-    if let Some(root_nodes) = &root_nodes.children {
-        for secondary_node in root_nodes {
-            if let Some(target) = &secondary_node.target {
-                walk_node(target, transforms, nodes, Vec2::ZERO);
+    while let Some(this_parent) = parent {
+        match transforms.get(&this_parent) {
+            Some(parent_c) => {
+                if parent_c.inner().is_dirty() {
+                    return true;
+                }
+                parent = parent_c.inner().parent_entity();
             }
+            None => break,
         }
     }
+
+    false
 }
 
 fn walk_node(
     entity: &Entity,
     transforms: &mut ComponentList<Transform>,
     nodes: &ComponentList<GraphNode>,
-    last_world_position: Vec2,
+    parent_transform: &Affine2,
 ) {
-    let new_world_position = if let Some(transform) = transforms.get_mut(entity) {
-        transform.inner_mut().update_world_position(last_world_position)
+    let world_transform = if let Some(transform) = transforms.get_mut(entity) {
+        transform.inner_mut().update_world_transform(parent_transform)
     } else {
-        last_world_position
+        *parent_transform
     };
 
     if let Some(this_node) = nodes.get(entity) {
         if let Some(children) = &this_node.inner().children {
             for child in children {
                 if let Some(target) = &child.target {
-                    walk_node(target, transforms, nodes, new_world_position);
+                    walk_node(target, transforms, nodes, &world_transform);
                 }
             }
         }
@@ -66,50 +242,26 @@ fn walk_node(
 type GraphInspectorLambda<'a> = &'a mut dyn FnMut(
     &Entity,
     &mut ComponentList<Name>,
-    &mut ComponentList<SerializationMarker>,
-    Option<SerializedEntity>,
     &ComponentList<PrefabMarker>,
     NameInspectorParameters,
 ) -> bool;
 
-pub fn walk_graph_inspect(
-    component_database: &mut super::ComponentDatabase,
-    singleton_database: &mut SingletonDatabase,
-    resources: &ResourcesDatabase,
-    f: GraphInspectorLambda<'_>,
-) {
-    let root_nodes = ROOT_NODES.lock().unwrap();
-
-    if let Some(root_nodes) = &root_nodes.children {
-        for secondary_node in root_nodes {
-            if let Some(target) = &secondary_node.target {
-                walk_node_inspect(target, component_database, singleton_database, resources, 0, f);
-            }
-        }
-    }
-}
-
 fn walk_node_inspect(
     entity: &Entity,
+    scene: &Scene,
     component_database: &mut ComponentDatabase,
     singleton_database: &mut SingletonDatabase,
     resources: &ResourcesDatabase,
     depth: usize,
     f: GraphInspectorLambda<'_>,
 ) {
-    // Unwrap our parts:
-    let current_se: Option<SerializedEntity> =
-        if let Some(se) = component_database.serialization_markers.get(entity) {
-            SerializedEntity::new(
-                entity,
-                se.inner().id,
-                component_database,
-                singleton_database,
-                resources,
-            )
-        } else {
-            None
-        };
+    let serialization_status = SerializedEntity::serialization_status(
+        entity,
+        scene,
+        component_database,
+        singleton_database,
+        resources,
+    );
 
     let mut show_children = true;
     let has_children: bool = component_database
@@ -130,13 +282,11 @@ fn walk_node_inspect(
         show_children = f(
             entity,
             &mut component_database.names,
-            &mut component_database.serialization_markers,
-            current_se,
             &component_database.prefab_markers,
             NameInspectorParameters {
                 depth,
                 has_children,
-                serialization_status: Default::default(),
+                serialization_status,
                 prefab_status: Default::default(),
                 being_inspected: Default::default(),
             },
@@ -153,6 +303,7 @@ fn walk_node_inspect(
                     if let Some(target) = &child.target {
                         walk_node_inspect(
                             target,
+                            scene,
                             component_database,
                             singleton_database,
                             resources,
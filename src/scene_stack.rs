@@ -0,0 +1,148 @@
+use super::{
+    systems::{grid_system::Grid, scene_system::SceneTransition, scheduler::SystemContext},
+    ActionMap, Ecs, Entity, ResourcesDatabase, SceneMode, WorldSnapshot,
+};
+use std::collections::HashSet;
+
+/// One loaded Scene on the SceneStack.
+pub struct SceneLayer {
+    pub ecs: Ecs,
+    pub grid: Grid,
+    /// If true, we keep running our systems while an overlay is on top of us.
+    pub update_below: bool,
+    /// The editor's selection, stashed while an overlay is on top of us.
+    pub stashed_selection: HashSet<Entity>,
+    /// The world as it was in Draft, taken when we started Playing.
+    draft_snapshot: Option<WorldSnapshot>,
+}
+
+impl SceneLayer {
+    pub fn new(ecs: Ecs, grid: Grid) -> Self {
+        Self {
+            ecs,
+            grid,
+            update_below: false,
+            stashed_selection: HashSet::new(),
+            draft_snapshot: None,
+        }
+    }
+
+    /// Leaving Draft snapshots the world, and going back to Draft restores it, along
+    /// with the `selection` we had when we left.
+    pub fn set_scene_mode(&mut self, scene_mode: SceneMode, selection: &mut HashSet<Entity>) {
+        let current_mode = self.ecs.scene_state.current_scene_mode();
+        if current_mode == scene_mode {
+            return;
+        }
+
+        if current_mode == SceneMode::Draft {
+            self.draft_snapshot = Some(WorldSnapshot::take(&self.ecs, &self.grid, selection));
+        } else if scene_mode == SceneMode::Draft {
+            match self.draft_snapshot.take() {
+                Some(snapshot) => *selection = snapshot.restore(&mut self.ecs, &mut self.grid),
+                None => error!(
+                    "Stopped {}, but it had no Draft snapshot to go back to!",
+                    self.ecs.scene_state.current()
+                ),
+            }
+        }
+
+        self.ecs.scene_state.set_scene_mode(scene_mode);
+    }
+
+    pub fn system_context<'a>(
+        &'a mut self,
+        action_map: &'a ActionMap,
+        resources: &'a ResourcesDatabase,
+        delta_time: f32,
+    ) -> SystemContext<'a> {
+        SystemContext {
+            ecs: &mut self.ecs,
+            grid: &mut self.grid,
+            action_map,
+            resources,
+            delta_time,
+        }
+    }
+}
+
+/// Every loaded Scene, bottom to top. The bottom is the level we're in, and anything
+/// above it is an overlay, like a pause menu or a dialogue box. Only the top Scene
+/// gets input and the editor.
+pub struct SceneStack {
+    layers: Vec<SceneLayer>,
+}
+
+impl SceneStack {
+    pub fn new(base: SceneLayer) -> Self {
+        Self { layers: vec![base] }
+    }
+
+    pub fn top(&self) -> &SceneLayer {
+        self.layers.last().unwrap()
+    }
+
+    pub fn top_mut(&mut self) -> &mut SceneLayer {
+        self.layers.last_mut().unwrap()
+    }
+
+    /// How many Scenes are on top of the base layer.
+    pub fn overlay_count(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Bottom to top, which is also the order we draw in.
+    pub fn layers_mut(&mut self) -> impl Iterator<Item = &mut SceneLayer> {
+        self.layers.iter_mut()
+    }
+
+    /// The top layer, and every layer beneath it which asked to keep updating.
+    /// The bool is true for the top layer.
+    pub fn updating_layers_mut(&mut self) -> impl Iterator<Item = (&mut SceneLayer, bool)> {
+        let top = self.layers.len() - 1;
+        self.layers
+            .iter_mut()
+            .enumerate()
+            .filter(move |(i, layer)| *i == top || layer.update_below)
+            .map(move |(i, layer)| (layer, i == top))
+    }
+
+    /// Finds the first requested SceneTransition, from the top of the stack down,
+    /// with the index of the layer which asked for it.
+    pub fn take_transition(&mut self) -> Option<(usize, SceneTransition)> {
+        self.layers
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find_map(|(i, layer)| layer.ecs.scene_state.take_transition().map(|t| (i, t)))
+    }
+
+    /// Unloads everything and starts over with a new base layer.
+    pub fn replace(&mut self, base: SceneLayer) {
+        self.layers.clear();
+        self.layers.push(base);
+    }
+
+    /// Covers the current top layer with an overlay.
+    pub fn push(&mut self, overlay: SceneLayer, update_below: bool, selection: HashSet<Entity>) {
+        let top = self.top_mut();
+        top.update_below = update_below;
+        top.stashed_selection = selection;
+
+        self.layers.push(overlay);
+    }
+
+    /// Unloads the layer at `index` and every overlay above it, and hands back the
+    /// selection the new top layer stashed. We never pop the base layer.
+    pub fn pop(&mut self, index: usize) -> Option<HashSet<Entity>> {
+        if index == 0 || index >= self.layers.len() {
+            return None;
+        }
+
+        self.layers.truncate(index);
+
+        let top = self.top_mut();
+        top.update_below = false;
+        Some(std::mem::take(&mut top.stashed_selection))
+    }
+}
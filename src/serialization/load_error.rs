@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// Something in a Scene, Prefab or singleton file which we couldn't load, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadError {
+    pub path: String,
+    /// Usually the Entity's Uuid, unless the Uuid is what's broken.
+    pub entity: Option<String>,
+    /// The key of the Component which wouldn't deserialize, like `transform`.
+    pub component: Option<String>,
+    /// Both start at 1, like in a text editor.
+    pub line_column: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl LoadError {
+    pub fn new(path: &str, message: impl fmt::Display) -> Self {
+        Self {
+            path: path.to_string(),
+            entity: None,
+            component: None,
+            line_column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// For serde_yaml's own errors, which know where they happened.
+    pub fn from_yaml(path: &str, error: serde_yaml::Error) -> Self {
+        let mut load_error = Self::new(path, &error);
+        load_error.line_column = error.location().map(|l| (l.line(), l.column()));
+        load_error
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        if let Some((line, column)) = self.line_column {
+            write!(f, ":{}:{}", line, column)?;
+        }
+        write!(f, ": ")?;
+
+        if let Some(entity) = &self.entity {
+            write!(f, "Entity {}", entity)?;
+            if let Some(component) = &self.component {
+                write!(f, "'s {}", component)?;
+            }
+            write!(f, ": ")?;
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
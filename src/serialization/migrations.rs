@@ -0,0 +1,423 @@
+use super::*;
+use anyhow::Result as AnyResult;
+use serde_yaml::{Mapping, Value as YamlValue};
+use std::collections::HashMap;
+
+/// The key every Scene, Prefab and singleton file keeps its schema version under.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// The version we save files as. Files without a version are from before we had versions,
+/// so they're version 0.
+pub const SCHEMA_VERSION: u64 = MIGRATIONS.len() as u64;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SchemaKind {
+    Scene,
+    Prefab,
+    Singletons,
+}
+
+pub struct Migration {
+    pub description: &'static str,
+    pub migrate: fn(&mut YamlValue, &MigrationContext<'_>) -> AnyResult<()>,
+}
+
+/// What a Migration gets to look at besides the file it's migrating.
+pub struct MigrationContext<'a> {
+    pub kind: SchemaKind,
+    /// Every Prefab file by id, as it was on disk before we migrated anything, so no
+    /// Migration depends on which files got migrated first.
+    pub prefabs: &'a HashMap<String, YamlValue>,
+}
+
+/// Every change we've made to the schema, in order. `MIGRATIONS[n]` takes a file from
+/// version `n` to version `n + 1`. When you rename or restructure a serialized field,
+/// add a Migration to the end of this list. Never edit one which has shipped!
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "Move Sprite scale and facing onto the Transform",
+        migrate: move_sprite_scale_to_transform,
+    },
+    Migration {
+        description: "List the fields each Prefab inheritor overrides",
+        migrate: list_prefab_overrides,
+    },
+];
+
+/// Takes the schema version off of a freshly loaded file, and runs every Migration it's
+/// missing. Returns true if the file needed migrating.
+pub fn migrate(file: &mut YamlValue, kind: SchemaKind) -> AnyResult<bool> {
+    let version = take_schema_version(file)?;
+
+    // Only old files need the Prefabs, so only they pay for loading them.
+    let prefabs = if version < SCHEMA_VERSION {
+        load_prefab_files()
+    } else {
+        HashMap::new()
+    };
+
+    let context = MigrationContext {
+        kind,
+        prefabs: &prefabs,
+    };
+    run_migrations(file, version, &context)
+}
+
+/// Like `migrate`, but with Prefabs someone already loaded.
+pub fn migrate_with(file: &mut YamlValue, context: &MigrationContext<'_>) -> AnyResult<bool> {
+    let version = take_schema_version(file)?;
+    run_migrations(file, version, context)
+}
+
+fn run_migrations(file: &mut YamlValue, version: u64, context: &MigrationContext<'_>) -> AnyResult<bool> {
+    if version > SCHEMA_VERSION {
+        bail!(
+            "it's schema version {}, but we only know up to {}. Is the game out of date?",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        (migration.migrate)(file, context)
+            .map_err(|e| format_err!("migration {} ({}) failed: {}", i + 1, migration.description, e))?;
+    }
+
+    Ok(version < SCHEMA_VERSION)
+}
+
+/// Puts the current schema version at the top of a file we're about to save.
+pub fn stamp(file: YamlValue) -> YamlValue {
+    match file {
+        YamlValue::Mapping(mapping) => {
+            let mut stamped = Mapping::new();
+            stamped.insert(SCHEMA_VERSION_KEY.into(), SCHEMA_VERSION.into());
+            for (key, value) in mapping {
+                stamped.insert(key, value);
+            }
+
+            YamlValue::Mapping(stamped)
+        }
+        file => file,
+    }
+}
+
+/// Migrates every Scene, Prefab and singleton file on disk, and saves the ones which changed.
+/// Run it with `cargo run -- --migrate`.
+pub fn migrate_all() -> AnyResult<()> {
+    let mut files = vec![];
+    for scene in serialization_util::scenes()? {
+        files.push((scene.entity_path(), SchemaKind::Scene));
+        files.push((scene.singleton_path(), SchemaKind::Singletons));
+    }
+    files.push((
+        serialization_util::singleton_components::default_path(),
+        SchemaKind::Singletons,
+    ));
+    for prefab_path in serialization_util::prefabs::prefab_paths()? {
+        files.push((prefab_path.to_string_lossy().to_string(), SchemaKind::Prefab));
+    }
+
+    // We load the Prefabs before saving anything, so every file sees them as they were.
+    let prefabs = load_prefab_files();
+
+    let mut migrated = 0;
+    let mut failed = 0;
+    for (path, kind) in files {
+        let context = MigrationContext {
+            kind,
+            prefabs: &prefabs,
+        };
+        match migrate_file(&path, &context) {
+            Ok(true) => {
+                info!("Migrated {}", path);
+                migrated += 1;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                error!("Couldn't migrate {}: {}", path, e);
+                failed += 1;
+            }
+        }
+    }
+
+    info!(
+        "Migrated {} files to schema version {}. {} failed.",
+        migrated, SCHEMA_VERSION, failed
+    );
+    Ok(())
+}
+
+fn migrate_file(path: &str, context: &MigrationContext<'_>) -> AnyResult<bool> {
+    let mut file: YamlValue = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+    let migrated = migrate_with(&mut file, context)?;
+    if migrated {
+        serialization_util::save_serialized_file(&stamp(file), path)?;
+    }
+
+    Ok(migrated)
+}
+
+/// Every Prefab file on disk by id, as YAML, without migrating them. A Prefab we can't
+/// read is left out.
+fn load_prefab_files() -> HashMap<String, YamlValue> {
+    let prefab_paths = match serialization_util::prefabs::prefab_paths() {
+        Ok(prefab_paths) => prefab_paths,
+        Err(e) => {
+            error!("Couldn't find the Prefabs to migrate against: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    prefab_paths
+        .into_iter()
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_string_lossy().to_string();
+            let text = std::fs::read_to_string(&path).ok()?;
+            Some((id, serde_yaml::from_str(&text).ok()?))
+        })
+        .collect()
+}
+
+fn take_schema_version(file: &mut YamlValue) -> AnyResult<u64> {
+    let version = match file {
+        YamlValue::Mapping(mapping) => mapping.remove(&SCHEMA_VERSION_KEY.into()),
+        // An empty file has nothing to migrate.
+        _ => return Ok(SCHEMA_VERSION),
+    };
+
+    match version {
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format_err!("its {} isn't a number", SCHEMA_VERSION_KEY)),
+        None => Ok(0),
+    }
+}
+
+/// The Serialized Entities in a Scene or Prefab file, by Uuid.
+pub fn serialized_entities_mut(file: &mut YamlValue, kind: SchemaKind) -> Option<&mut Mapping> {
+    match kind {
+        SchemaKind::Scene => file.as_mapping_mut(),
+        SchemaKind::Prefab => file.get_mut("members")?.as_mapping_mut(),
+        SchemaKind::Singletons => None,
+    }
+}
+
+/// Runs `f` on every Serialized Entity in a Scene or Prefab file.
+fn for_each_serialized_entity(
+    file: &mut YamlValue,
+    kind: SchemaKind,
+    mut f: impl FnMut(&mut YamlValue) -> AnyResult<()>,
+) -> AnyResult<()> {
+    if let Some(entities) = serialized_entities_mut(file, kind) {
+        for (_, entity) in entities.iter_mut() {
+            f(entity)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The `inner` of one of a Serialized Entity's Components, if it has it.
+fn component_mut<'a>(entity: &'a mut YamlValue, component: &str) -> Option<&'a mut Mapping> {
+    entity.get_mut(component)?.get_mut("inner")?.as_mapping_mut()
+}
+
+/// Sprites used to have their own scale, and flipped to face Left or Down. Now the
+/// Transform does all of that, with a negative scale to flip. All of our sprite sheets
+/// face Right and Up, so facing any other way becomes a flip.
+fn move_sprite_scale_to_transform(file: &mut YamlValue, context: &MigrationContext<'_>) -> AnyResult<()> {
+    for_each_serialized_entity(file, context.kind, |entity| {
+        let running_data = match component_mut(entity, "sprite")
+            .and_then(|sprite| sprite.get_mut(&"running_data".into()))
+            .and_then(YamlValue::as_mapping_mut)
+        {
+            Some(running_data) => running_data,
+            None => return Ok(()),
+        };
+
+        let mut scale = match running_data.remove(&"scale".into()) {
+            Some(scale) => [
+                scale["x"].as_f64().unwrap_or(1.0),
+                scale["y"].as_f64().unwrap_or(1.0),
+            ],
+            None => [1.0, 1.0],
+        };
+
+        if running_data.remove(&"facing_horizontal".into()) == Some("Left".into()) {
+            scale[0] = -scale[0];
+        }
+        if running_data.remove(&"facing_vertical".into()) == Some("Down".into()) {
+            scale[1] = -scale[1];
+        }
+
+        if scale == [1.0, 1.0] {
+            return Ok(());
+        }
+
+        // Without a Transform, the Sprite was never drawn anyway.
+        if let Some(transform) = component_mut(entity, "transform") {
+            let local_scale = transform.get(&"local_scale".into());
+            let local_scale = [
+                local_scale.and_then(|s| s["x"].as_f64()).unwrap_or(1.0) * scale[0],
+                local_scale.and_then(|s| s["y"].as_f64()).unwrap_or(1.0) * scale[1],
+            ];
+
+            let mut vec2 = Mapping::new();
+            vec2.insert("x".into(), local_scale[0].into());
+            vec2.insert("y".into(), local_scale[1].into());
+            transform.insert("local_scale".into(), YamlValue::Mapping(vec2));
+        }
+
+        Ok(())
+    })
+}
+
+/// Prefab inheritors used to override whole Components. Now they list the fields they
+/// override in `prefab_overrides`, and inherit the rest, so we find those fields by diffing
+/// each inheritor against the Prefab member it inherits. If the context doesn't have the
+/// Prefab, or the member doesn't have the Component itself, the Component stays overridden whole.
+fn list_prefab_overrides(file: &mut YamlValue, context: &MigrationContext<'_>) -> AnyResult<()> {
+    // This is MIGRATIONS[1], so the Prefabs we diff against need to be at version 1 too.
+    const VERSION: usize = 1;
+    let mut prefabs: HashMap<String, Option<YamlValue>> = HashMap::new();
+
+    for_each_serialized_entity(file, context.kind, |entity| {
+        // Saved by a game which already listed them.
+        if entity.get("prefab_overrides").is_some() {
+            return Ok(());
+        }
+
+        let marker = &entity["prefab_marker"]["inner"];
+        let (main_id, sub_id) = match (marker["main_id"].as_str(), marker["sub_id"].as_str()) {
+            (Some(main_id), Some(sub_id)) => (main_id.to_string(), sub_id.to_string()),
+            _ => return Ok(()),
+        };
+
+        let prefab = prefabs
+            .entry(main_id.clone())
+            .or_insert_with(|| prefab_at_version(&main_id, VERSION, context));
+        let base = match prefab
+            .as_ref()
+            .and_then(|prefab| prefab["members"].get(sub_id.as_str()))
+        {
+            Some(base) => base,
+            None => return Ok(()),
+        };
+
+        let overrides = prefab_overrides_against(entity, base);
+        if overrides.is_empty() == false {
+            if let Some(entity) = entity.as_mapping_mut() {
+                entity.insert("prefab_overrides".into(), YamlValue::Sequence(overrides));
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// The paths where a Serialized Entity's Components differ from the `base`'s, for
+/// Components they both have.
+fn prefab_overrides_against(entity: &YamlValue, base: &YamlValue) -> Vec<YamlValue> {
+    const NOT_COMPONENTS: [&str; 4] = ["id", "marker", "prefab_marker", "prefab_overrides"];
+
+    let mut overrides = vec![];
+    for (key, component) in entity.as_mapping().into_iter().flatten() {
+        let key = match key.as_str() {
+            Some(key) if NOT_COMPONENTS.contains(&key) == false => key,
+            _ => continue,
+        };
+
+        let base_component = &base[key];
+        if component.is_null() == false && base_component.is_null() == false {
+            overrides.extend(prefab_system::changed_paths(key, base_component, component));
+        }
+    }
+
+    overrides.sort();
+    overrides.into_iter().map(YamlValue::from).collect()
+}
+
+/// One of the context's Prefabs, migrated up to `version` but no further. `None` if the
+/// context doesn't have it, or it won't migrate.
+fn prefab_at_version(id: &str, version: usize, context: &MigrationContext<'_>) -> Option<YamlValue> {
+    let mut prefab = context.prefabs.get(id)?.clone();
+    let prefab_version = take_schema_version(&mut prefab).ok()? as usize;
+
+    let prefab_context = MigrationContext {
+        kind: SchemaKind::Prefab,
+        prefabs: context.prefabs,
+    };
+    for migration in MIGRATIONS.iter().take(version).skip(prefab_version) {
+        (migration.migrate)(&mut prefab, &prefab_context).ok()?;
+    }
+
+    Some(prefab)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefab_overrides_are_the_changed_fields() {
+        let base: YamlValue = serde_yaml::from_str(
+            "
+            name: {inner: {name: Box}, active: true}
+            sprite: {inner: {sprite_name: box, tint: red}, active: true}
+            ",
+        )
+        .unwrap();
+        let entity: YamlValue = serde_yaml::from_str(
+            "
+            prefab_marker: {inner: {main_id: a, sub_id: a}, active: true}
+            sprite: {inner: {sprite_name: crate, tint: red}, active: true}
+            velocity: {inner: {x: 1}, active: true}
+            ",
+        )
+        .unwrap();
+
+        // The Velocity isn't on the Prefab, so it's overridden whole, and not listed.
+        assert_eq!(
+            prefab_overrides_against(&entity, &base),
+            vec![YamlValue::from("sprite.inner.sprite_name")]
+        );
+    }
+
+    #[test]
+    fn prefab_overrides_come_from_the_context() {
+        let prefab: YamlValue = serde_yaml::from_str(
+            "
+            schema_version: 1
+            members:
+              b: {name: {inner: {name: Box}, active: true}}
+            ",
+        )
+        .unwrap();
+        let mut scene: YamlValue = serde_yaml::from_str(
+            "
+            c:
+              prefab_marker: {inner: {main_id: a, sub_id: b}, active: true}
+              name: {inner: {name: Crate}, active: true}
+            d:
+              prefab_marker: {inner: {main_id: missing, sub_id: b}, active: true}
+              name: {inner: {name: Crate}, active: true}
+            ",
+        )
+        .unwrap();
+
+        let prefabs = vec![("a".to_string(), prefab)].into_iter().collect();
+        let context = MigrationContext {
+            kind: SchemaKind::Scene,
+            prefabs: &prefabs,
+        };
+        list_prefab_overrides(&mut scene, &context).unwrap();
+
+        assert_eq!(
+            scene["c"]["prefab_overrides"],
+            YamlValue::Sequence(vec!["name.inner.name".into()])
+        );
+        // Without its Prefab, the Name stays overridden whole.
+        assert!(scene["d"].get("prefab_overrides").is_none());
+    }
+}
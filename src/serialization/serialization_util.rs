@@ -1,8 +1,23 @@
 use super::*;
 
 use anyhow::{Error, Result as AnyResult};
+use migrations::SchemaKind;
+use serde_yaml::Value as YamlValue;
 use std::fs;
 
+pub const COOKED_DIRECTORY: &str = "assets/gen/cooked";
+
+/// A hash of every serialized type's source, made by the build script. See `CookedHeader`.
+const COOKED_LAYOUT_HASH: &str = env!("COOKED_LAYOUT_HASH");
+
+mod cook;
+pub mod cooking {
+    pub use super::cook::*;
+}
+
+mod entity_file;
+use entity_file::EntityFile;
+
 mod serialize_entities;
 pub mod entities {
     pub use super::serialize_entities::*;
@@ -13,8 +28,10 @@ pub mod game_config {
     pub use super::serialize_game_config::*;
 }
 
+mod prefab_usages;
 mod serialize_prefabs;
 pub mod prefabs {
+    pub use super::prefab_usages::*;
     pub use super::serialize_prefabs::*;
 }
 
@@ -61,32 +78,153 @@ where
     Ok(fs::write(path, s)?)
 }
 
-// fn load_file_bin<T: Default>(path: &str) -> Result<T, Error>
-// where
-//     for<'de> T: serde::Deserialize<'de>,
-// {
-//     let path = std::path::Path::new(path);
-//     fs::create_dir_all(path.parent().unwrap())?;
+/// Reads a YAML file. Unlike `load_serialized_file`, a file which is missing or doesn't
+/// parse is an error, and the error says where the problem is.
+pub(super) fn read_yaml_file(path: &str) -> Result<(String, YamlValue), LoadError> {
+    let text = fs::read_to_string(path).map_err(|e| LoadError::new(path, e))?;
+    if text.trim().is_empty() {
+        return Ok((text, YamlValue::Null));
+    }
+
+    let file = serde_yaml::from_str(&text).map_err(|e| LoadError::from_yaml(path, e))?;
+    Ok((text, file))
+}
+
+/// Loads a Scene, Prefab or singleton file as YAML, running any migrations it needs first.
+pub(super) fn load_versioned_value(path: &str, kind: SchemaKind) -> Result<YamlValue, Error> {
+    let (_, mut file) = read_yaml_file(path)?;
+    migrations::migrate(&mut file, kind).map_err(|e| LoadError::new(path, e))?;
+
+    Ok(file)
+}
+
+/// Saves a Scene, Prefab or singleton file, stamped with the current schema version.
+pub fn save_versioned_file<T>(item: &T, path: &str) -> Result<(), Error>
+where
+    T: serde::Serialize,
+{
+    save_serialized_file(&migrations::stamp(serde_yaml::to_value(item)?), path)
+}
+
+/// Every Scene in the scenes directory.
+pub fn scenes() -> AnyResult<Vec<Scene>> {
+    let mut scenes = vec![];
+    for scene_directory in fs::read_dir(SCENE_DIRECTORY)? {
+        let scene_directory = scene_directory?;
+        if scene_directory.file_type()?.is_dir() {
+            scenes.push(Scene::new(
+                scene_directory.file_name().to_string_lossy().to_string(),
+            ));
+        }
+    }
+
+    Ok(scenes)
+}
+
+/// Every Scene and Prefab file on disk, with which kind of file it is.
+pub fn entity_files() -> AnyResult<Vec<(String, SchemaKind)>> {
+    let mut files = vec![];
+    for scene in scenes()? {
+        files.push((scene.entity_path(), SchemaKind::Scene));
+    }
+    for prefab_path in prefabs::prefab_paths()? {
+        files.push((prefab_path.to_string_lossy().to_string(), SchemaKind::Prefab));
+    }
+
+    Ok(files)
+}
+
+/// Where the cooked, binary copy of a serialized file lives. Cooked files are kept apart
+/// by schema version, so we never load one cooked from an older schema. Within a schema
+/// version, the `CookedHeader` tells us if the layout changed.
+pub fn cooked_path(path: &str) -> String {
+    format!(
+        "{}/v{}/{}.bin",
+        COOKED_DIRECTORY,
+        migrations::SCHEMA_VERSION,
+        path.trim_start_matches("assets/")
+    )
+}
+
+/// Loads the cooked copy of the file at `path`, unless any of the `sources` it was cooked
+/// from have been edited since, or it was cooked with a different layout. A cooked file
+/// which won't load gets skipped, with an error.
+pub(super) fn load_fresh_cooked_file<T>(path: &str, sources: &[&str]) -> Option<T>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    let cooked_path = cooked_path(path);
+    let cooked_modified = fs::metadata(&cooked_path).and_then(|m| m.modified()).ok()?;
+
+    for source in sources {
+        if let Ok(source_modified) = fs::metadata(source).and_then(|m| m.modified()) {
+            if source_modified > cooked_modified {
+                return None;
+            }
+        }
+    }
+
+    match load_file_bin(&cooked_path) {
+        Ok(Some(item)) => Some(item),
+        Ok(None) => {
+            info!(
+                "{} was cooked with a different layout, so we're loading the YAML instead. Cook it again with `--cook`.",
+                cooked_path
+            );
+            None
+        }
+        Err(e) => {
+            error!(
+                "Couldn't load {}, so we're loading the YAML instead: {}",
+                cooked_path, e
+            );
+            None
+        }
+    }
+}
+
+/// Every cooked file starts with one of these. Bincode can't tell if the types changed
+/// since a file was cooked, and will happily read garbage, so we check this first.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct CookedHeader {
+    layout_hash: String,
+    type_name: String,
+}
+
+impl CookedHeader {
+    fn current<T>() -> Self {
+        Self {
+            layout_hash: COOKED_LAYOUT_HASH.to_owned(),
+            type_name: std::any::type_name::<T>().to_owned(),
+        }
+    }
+}
 
-//     if path.exists() == false {
-//         fs::File::create(path)?;
-//     }
+/// Gives back `None` if the file was cooked with a different layout, or as a different type.
+fn load_file_bin<T>(path: &str) -> Result<Option<T>, Error>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    let file_bits: Vec<u8> = fs::read(path)?;
+    let mut reader = file_bits.as_slice();
 
-//     let file_bits: Vec<u8> = fs::read(path)?;
-//     Ok(bincode::deserialize(&file_bits).unwrap_or_default())
-// }
+    // A file from before we had headers could claim any length here, so we cap it.
+    let header: CookedHeader = bincode::config().limit(1024).deserialize_from(&mut reader)?;
+    if header != CookedHeader::current::<T>() {
+        return Ok(None);
+    }
 
-// fn save_file_bin<T>(item: &T, path: &str) -> Result<(), Error>
-// where
-//     T: serde::Serialize,
-// {
-//     let path = std::path::Path::new(path);
-//     fs::create_dir_all(path.parent().unwrap())?;
+    Ok(Some(bincode::deserialize(reader)?))
+}
 
-//     if path.exists() == false {
-//         fs::File::create(path)?;
-//     }
+fn save_file_bin<T>(item: &T, path: &str) -> Result<(), Error>
+where
+    T: serde::Serialize,
+{
+    let path = std::path::Path::new(path);
+    fs::create_dir_all(path.parent().unwrap())?;
 
-//     let s = bincode::serialize(item)?;
-//     Ok(fs::write(path, s)?)
-// }
+    let mut s = bincode::serialize(&CookedHeader::current::<T>())?;
+    s.extend(bincode::serialize(item)?);
+    Ok(fs::write(path, s)?)
+}
@@ -0,0 +1,103 @@
+use super::*;
+use serde_yaml::Value as YamlValue;
+
+/// Converts every Scene, Prefab and singleton file into the binary format the game
+/// prefers at runtime. Run it with `cargo run --release -- --cook`. A file which doesn't
+/// survive the round trip through the binary format isn't cooked, so the game keeps
+/// loading its YAML.
+pub fn cook_all() -> AnyResult<()> {
+    let mut cooked = 0;
+    let mut failed = 0;
+
+    let mut cook = |path: &str, result: AnyResult<()>| match result {
+        Ok(()) => {
+            info!("Cooked {}", path);
+            cooked += 1;
+        }
+        Err(e) => {
+            error!("Couldn't cook {}: {}", path, e);
+            failed += 1;
+        }
+    };
+
+    for scene in scenes()? {
+        let entity_path = scene.entity_path();
+        cook(
+            &entity_path,
+            load_strict(&entity_path, SchemaKind::Scene)
+                .and_then(|entity_file| cook_file(&entity_file.entities, &entity_path)),
+        );
+
+        let singleton_path = scene.singleton_path();
+        cook(
+            &singleton_path,
+            singleton_components::load_serialized_singleton_database(&scene)
+                .and_then(|singletons| cook_file(&singletons, &singleton_path)),
+        );
+    }
+
+    for prefab_path in prefabs::prefab_paths()? {
+        let prefab_path = prefab_path.to_string_lossy().to_string();
+        cook(
+            &prefab_path,
+            load_strict(&prefab_path, SchemaKind::Prefab)
+                .and_then(|prefab_file| prefab_file.prefab())
+                .and_then(|prefab| cook_file(&prefab, &prefab_path)),
+        );
+    }
+
+    info!("Cooked {} files. {} failed.", cooked, failed);
+    Ok(())
+}
+
+/// Checks that `item` comes back out of the binary format the same as it went in, by
+/// comparing it as YAML before and after.
+pub fn verify_round_trip<T>(item: &T) -> AnyResult<()>
+where
+    T: serde::Serialize,
+    for<'de> T: serde::Deserialize<'de>,
+{
+    let before = serde_yaml::to_value(item)?;
+    let cooked: T = bincode::deserialize(&bincode::serialize(item)?)?;
+    let after = serde_yaml::to_value(&cooked)?;
+
+    if before == after {
+        return Ok(());
+    }
+
+    // Point at the first top level key which changed, since whole files are big.
+    let changed_key = match (&before, &after) {
+        (YamlValue::Mapping(before), YamlValue::Mapping(after)) => before
+            .iter()
+            .find(|(key, value)| after.get(key) != Some(*value))
+            .map(|(key, _)| serde_yaml::to_string(key).unwrap_or_default()),
+        _ => None,
+    };
+
+    match changed_key {
+        Some(key) => bail!(
+            "it doesn't survive the round trip. {} changed.",
+            key.trim_start_matches("---").trim()
+        ),
+        None => bail!("it doesn't survive the round trip."),
+    }
+}
+
+fn cook_file<T>(item: &T, path: &str) -> AnyResult<()>
+where
+    T: serde::Serialize,
+    for<'de> T: serde::Deserialize<'de>,
+{
+    verify_round_trip(item)?;
+    save_file_bin(item, &cooked_path(path))
+}
+
+/// A file with quarantined Entities doesn't get cooked, so we never cook an Entity away.
+fn load_strict(path: &str, kind: SchemaKind) -> AnyResult<EntityFile> {
+    let entity_file = EntityFile::load(path, kind)?;
+    if let Some(load_error) = entity_file.errors.first() {
+        bail!("{}", load_error);
+    }
+
+    Ok(entity_file)
+}
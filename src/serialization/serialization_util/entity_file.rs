@@ -0,0 +1,253 @@
+use super::*;
+use serde_yaml::Mapping;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A Scene or Prefab file, loaded strictly. Entities which won't deserialize get quarantined:
+/// we leave them out of the Scene, but keep their YAML, so saving the file doesn't lose them.
+pub struct EntityFile {
+    path: String,
+    kind: SchemaKind,
+    /// Everything in the file besides its Serialized Entities, like a Prefab's `root_id`.
+    shell: YamlValue,
+    pub entities: HashMap<Uuid, SerializedEntity>,
+    quarantined: Mapping,
+    /// Why we quarantined each Entity we did.
+    pub errors: Vec<LoadError>,
+}
+
+impl EntityFile {
+    /// A file which isn't on disk yet.
+    pub fn new(path: &str, kind: SchemaKind) -> Self {
+        EntityFile {
+            path: path.to_string(),
+            kind,
+            shell: YamlValue::Null,
+            entities: HashMap::new(),
+            quarantined: Mapping::new(),
+            errors: vec![],
+        }
+    }
+
+    pub fn load(path: &str, kind: SchemaKind) -> Result<Self, Error> {
+        let (text, mut shell) = read_yaml_file(path)?;
+        migrations::migrate(&mut shell, kind).map_err(|e| LoadError::new(path, e))?;
+
+        let serialized_entities = migrations::serialized_entities_mut(&mut shell, kind)
+            .map(std::mem::take)
+            .unwrap_or_default();
+
+        let mut entity_file = EntityFile::new(path, kind);
+        entity_file.shell = shell;
+        let known_keys = known_component_keys()?;
+
+        for (key, value) in serialized_entities {
+            let entity_key = key
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{:?}", key));
+
+            let mut load_error = match key.as_str().map(Uuid::parse_str) {
+                Some(Ok(id)) => match unknown_component_key(&value, &known_keys) {
+                    // Serde would skip over a typo like `transfrom:`, and lose that Component.
+                    Some(component_key) => {
+                        let mut load_error = LoadError::new(path, "there's no Component by that name");
+                        load_error.line_column = locate_component_key(&text, &entity_key, &component_key);
+                        load_error.component = Some(component_key);
+                        load_error
+                    }
+                    None => match serde_yaml::from_value(value.clone()) {
+                        Ok(serialized_entity) => {
+                            entity_file.entities.insert(id, serialized_entity);
+                            continue;
+                        }
+                        Err(e) => {
+                            let mut load_error = LoadError::new(path, e);
+                            load_error.component = broken_component(&value);
+                            load_error.line_column = locate_entity_error(&text, &entity_key);
+                            load_error
+                        }
+                    },
+                },
+                _ => LoadError::new(path, "its key isn't a Uuid"),
+            };
+
+            load_error.entity = Some(entity_key);
+            entity_file.errors.push(load_error);
+            entity_file.quarantined.insert(key, value);
+        }
+
+        Ok(entity_file)
+    }
+
+    /// Saves our Entities, and puts back every Entity we quarantined. Entities are sorted
+    /// by Uuid, so saving the same Scene twice writes the same file.
+    pub fn save(&self) -> Result<(), Error> {
+        let mut sorted_entities = vec![];
+        for (id, serialized_entity) in self.entities.iter() {
+            let mut serialized_entity = serde_yaml::to_value(serialized_entity)?;
+            compact(&mut serialized_entity);
+            sorted_entities.push((YamlValue::from(id.to_string()), serialized_entity));
+        }
+        for (key, value) in self.quarantined.iter() {
+            sorted_entities.push((key.clone(), value.clone()));
+        }
+        sorted_entities.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+
+        let mut entities = Mapping::new();
+        for (key, value) in sorted_entities {
+            entities.insert(key, value);
+        }
+
+        let file = match self.kind {
+            SchemaKind::Prefab => {
+                let mut shell = self.shell.as_mapping().cloned().unwrap_or_default();
+                shell.insert("members".into(), YamlValue::Mapping(entities));
+                YamlValue::Mapping(shell)
+            }
+            SchemaKind::Scene | SchemaKind::Singletons => YamlValue::Mapping(entities),
+        };
+
+        save_serialized_file(&migrations::stamp(file), &self.path)
+    }
+
+    /// The Prefab in a Prefab file, minus any members we quarantined.
+    pub fn prefab(&self) -> Result<Prefab, Error> {
+        let mut prefab: Prefab =
+            serde_yaml::from_value(self.shell.clone()).map_err(|e| LoadError::new(&self.path, e))?;
+        prefab.members = self.entities.clone();
+
+        Ok(prefab)
+    }
+
+    /// Takes the Prefab's members, keeping whatever we quarantined.
+    pub fn set_prefab(&mut self, prefab: &Prefab) -> Result<(), Error> {
+        self.shell = serde_yaml::to_value(prefab)?;
+        if let Some(members) = migrations::serialized_entities_mut(&mut self.shell, SchemaKind::Prefab) {
+            members.clear();
+        }
+        self.entities = prefab.members.clone();
+
+        Ok(())
+    }
+}
+
+/// Leaves out whatever loading fills back in anyway: absent Components, an empty list of
+/// Prefab overrides, and the parts of a Sprite's animation which start over when the game runs.
+fn compact(serialized_entity: &mut YamlValue) {
+    const SPRITE_RUNTIME_FIELDS: [&str; 3] = ["current_frame", "frame_time", "is_animating"];
+
+    let fields = match serialized_entity.as_mapping_mut() {
+        Some(fields) => fields,
+        None => return,
+    };
+
+    let no_prefab_overrides = YamlValue::Sequence(vec![]);
+    let absent: Vec<YamlValue> = fields
+        .iter()
+        .filter(|(key, value)| {
+            value.is_null() || (key.as_str() == Some("prefab_overrides") && **value == no_prefab_overrides)
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in absent {
+        fields.remove(&key);
+    }
+
+    let running_data = fields
+        .get_mut(&"sprite".into())
+        .and_then(|sprite| sprite.get_mut("inner"))
+        .and_then(|sprite| sprite.get_mut("running_data"))
+        .and_then(YamlValue::as_mapping_mut);
+
+    if let Some(running_data) = running_data {
+        if let Ok(default) = serde_yaml::to_value(SpriteRunningData::default()) {
+            for field in SPRITE_RUNTIME_FIELDS.iter() {
+                let key = YamlValue::from(*field);
+                if running_data.get(&key) == default.get(*field) {
+                    running_data.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Which of an Entity's Components won't deserialize, found by trying them one at a time.
+fn broken_component(serialized_entity: &YamlValue) -> Option<String> {
+    let components = serialized_entity.as_mapping()?;
+    components
+        .iter()
+        .find(|(key, value)| {
+            let mut component = Mapping::new();
+            component.insert((*key).clone(), (*value).clone());
+            serde_yaml::from_value::<SerializedEntity>(YamlValue::Mapping(component)).is_err()
+        })
+        .and_then(|(key, _)| key.as_str().map(str::to_string))
+}
+
+/// Every key a Serialized Entity can have, like `transform` or `prefab_overrides`.
+fn known_component_keys() -> Result<Vec<YamlValue>, Error> {
+    let default = serde_yaml::to_value(SerializedEntity::default())?;
+    Ok(default
+        .as_mapping()
+        .map(|fields| fields.iter().map(|(key, _)| key.clone()).collect())
+        .unwrap_or_default())
+}
+
+/// The first key in this Entity which isn't a Component (or anything else we know about).
+fn unknown_component_key(serialized_entity: &YamlValue, known_keys: &[YamlValue]) -> Option<String> {
+    serialized_entity
+        .as_mapping()?
+        .iter()
+        .find(|(key, _)| known_keys.contains(key) == false)
+        .map(|(key, _)| {
+            key.as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{:?}", key))
+        })
+}
+
+/// Once we've loaded a file, serde can't tell us where anything was, so we find the
+/// Entity's lines in the text and deserialize just those again.
+fn locate_entity_error(text: &str, entity_key: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let (start, end) = entity_lines(&lines, entity_key)?;
+
+    let entity_text = lines[start..end].join("\n");
+    let error = serde_yaml::from_str::<HashMap<YamlValue, SerializedEntity>>(&entity_text).err()?;
+    let location = error.location()?;
+
+    Some((start + location.line(), location.column()))
+}
+
+/// Where a key is within the Entity's lines. Both start at 1, like a LoadError's.
+fn locate_component_key(text: &str, entity_key: &str, component_key: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let (start, end) = entity_lines(&lines, entity_key)?;
+
+    (start + 1..end)
+        .find(|i| starts_with_key(lines[*i], component_key))
+        .map(|i| (i + 1, indentation(lines[i]) + 1))
+}
+
+/// The range of lines the Entity takes up in the file, starting with its key.
+fn entity_lines(lines: &[&str], entity_key: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| starts_with_key(line, entity_key))?;
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim().is_empty() == false && indentation(line) <= indentation(lines[start]))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    Some((start, end))
+}
+
+fn starts_with_key(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(key)
+        .map_or(false, |rest| rest.starts_with(':'))
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
@@ -0,0 +1,191 @@
+use super::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How an Entity uses a Prefab.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefabUsageKind {
+    /// It's an instance of the Prefab, or a variant of it.
+    Instance,
+    /// It's an instance of one of the Prefab's members other than its root, like the child
+    /// of an instance. This is the member's Uuid.
+    MemberInstance(Uuid),
+    /// One of its Components refers to the Prefab, in the field named here.
+    Reference(&'static str),
+}
+
+/// One Entity, in one Scene or Prefab file, which uses a Prefab.
+#[derive(Debug, Clone)]
+pub struct PrefabUsage {
+    pub path: String,
+    pub kind: SchemaKind,
+    pub entity: Uuid,
+    pub entity_name: Option<String>,
+    pub usage: PrefabUsageKind,
+}
+
+/// Which Scenes and Prefabs use each Prefab, found by reading every one of them on disk.
+/// A Prefab using itself doesn't count, since it goes wherever the Prefab goes.
+#[derive(Debug, Clone, Default)]
+pub struct PrefabDependencies {
+    pub usages: HashMap<Uuid, Vec<PrefabUsage>>,
+    /// Files we couldn't load, so couldn't check. Why, for each.
+    pub unchecked_files: Vec<String>,
+}
+
+impl PrefabDependencies {
+    pub fn build() -> AnyResult<Self> {
+        let mut dependencies = PrefabDependencies::default();
+
+        for (path, kind) in entity_files()? {
+            let entity_file = match EntityFile::load(&path, kind) {
+                Ok(entity_file) => entity_file,
+                Err(e) => {
+                    dependencies.unchecked_files.push(e.to_string());
+                    continue;
+                }
+            };
+
+            let own_prefab = match kind {
+                SchemaKind::Prefab => entity_file.prefab().ok().map(|prefab| prefab.root_id()),
+                _ => None,
+            };
+
+            for (_, mut serialized_entity) in entity_file.entities {
+                for (prefab_id, usage) in entity_usages(&mut serialized_entity) {
+                    if Some(prefab_id) == own_prefab {
+                        continue;
+                    }
+
+                    dependencies
+                        .usages
+                        .entry(prefab_id)
+                        .or_default()
+                        .push(PrefabUsage {
+                            path: path.clone(),
+                            kind,
+                            entity: serialized_entity.id,
+                            entity_name: serialized_entity
+                                .name
+                                .as_ref()
+                                .map(|name| name.inner.name.clone()),
+                            usage,
+                        });
+                }
+            }
+        }
+
+        for usages in dependencies.usages.values_mut() {
+            usages.sort_by(|lhs, rhs| (&lhs.path, lhs.entity).cmp(&(&rhs.path, rhs.entity)));
+        }
+
+        Ok(dependencies)
+    }
+}
+
+/// Every Prefab a SerializedEntity uses, and how.
+fn entity_usages(serialized_entity: &mut SerializedEntity) -> Vec<(Uuid, PrefabUsageKind)> {
+    let mut usages = vec![];
+
+    if let Some(prefab_marker) = &serialized_entity.prefab_marker {
+        let prefab_marker = &prefab_marker.inner;
+        let usage = if prefab_marker.main_id() == prefab_marker.sub_id() {
+            PrefabUsageKind::Instance
+        } else {
+            PrefabUsageKind::MemberInstance(prefab_marker.sub_id())
+        };
+
+        usages.push((prefab_marker.main_id(), usage));
+    }
+
+    serialized_entity.foreach_component_mut(|component| {
+        for (field, reference) in component.prefab_references() {
+            if let Some(target) = reference.target {
+                usages.push((target, PrefabUsageKind::Reference(field)));
+            }
+        }
+    });
+
+    usages
+}
+
+/// Points every file in `usages` at Prefab `new_id` instead of `old_id`, and returns the
+/// Prefabs we changed, for the caller to cache. Instances of the old root become instances
+/// of the new one. Instances of its other members have nothing to match up with, so they're
+/// unpacked, keeping everything they inherited. `prefabs` still needs the old Prefab in it.
+pub fn replace_prefab(
+    usages: &[PrefabUsage],
+    old_id: Uuid,
+    new_id: Uuid,
+    prefabs: &PrefabMap,
+) -> AnyResult<Vec<Prefab>> {
+    let mut files: Vec<(&str, SchemaKind)> = usages
+        .iter()
+        .map(|usage| (usage.path.as_str(), usage.kind))
+        .collect();
+    files.sort_by_key(|(path, _)| *path);
+    files.dedup_by_key(|(path, _)| *path);
+
+    let mut changed_prefabs = vec![];
+    for (path, kind) in files {
+        let mut entity_file = EntityFile::load(path, kind)?;
+        let mut changed = false;
+
+        for serialized_entity in entity_file.entities.values_mut() {
+            changed |= replace_in_entity(serialized_entity, old_id, new_id, prefabs)?;
+        }
+
+        if changed {
+            entity_file.save()?;
+            if kind == SchemaKind::Prefab {
+                changed_prefabs.push(entity_file.prefab()?);
+            }
+        }
+    }
+
+    Ok(changed_prefabs)
+}
+
+fn replace_in_entity(
+    serialized_entity: &mut SerializedEntity,
+    old_id: Uuid,
+    new_id: Uuid,
+    prefabs: &PrefabMap,
+) -> AnyResult<bool> {
+    let mut changed = false;
+
+    let old_marker = serialized_entity
+        .prefab_marker
+        .as_ref()
+        .map(|pm| pm.inner.clone())
+        .filter(|pm| pm.main_id() == old_id);
+
+    if let Some(old_marker) = old_marker {
+        if old_marker.sub_id() == old_id {
+            if let Some(prefab_marker) = &mut serialized_entity.prefab_marker {
+                prefab_marker.inner = PrefabMarker::new_main(new_id);
+            }
+        } else {
+            let member =
+                prefab_system::resolve_prefab_member(prefabs, old_marker.main_id(), old_marker.sub_id())?;
+            let mut unpacked = prefab_system::load_override_into_prefab(member, serialized_entity.clone())?;
+            unpacked.prefab_marker = None;
+            unpacked.prefab_overrides.clear();
+
+            *serialized_entity = unpacked;
+        }
+
+        changed = true;
+    }
+
+    serialized_entity.foreach_component_mut(|component| {
+        for (_, reference) in component.prefab_references() {
+            if reference.target == Some(old_id) {
+                reference.target = Some(new_id);
+                changed = true;
+            }
+        }
+    });
+
+    Ok(changed)
+}
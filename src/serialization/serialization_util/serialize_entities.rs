@@ -5,44 +5,61 @@ use super::{
 use std::collections::HashMap;
 use uuid::Uuid;
 
-pub fn path() -> (String, bool) {
-    let scene: &Scene = &scene_system::CURRENT_SCENE.lock().unwrap();
+pub fn path(scene: &Scene) -> (String, bool) {
     (scene.entity_path(), scene.is_prefab())
 }
 
-pub fn load_all_entities() -> Result<HashMap<Uuid, SerializedEntity>, Error> {
-    let (scene_entity_path, is_prefab) = path();
+pub fn load_all_entities(scene: &Scene) -> Result<HashMap<Uuid, SerializedEntity>, Error> {
+    Ok(load_all_entities_checked(scene)?.0)
+}
+
+/// Like `load_all_entities`, but also says why we quarantined any Entities we couldn't load.
+pub fn load_all_entities_checked(
+    scene: &Scene,
+) -> Result<(HashMap<Uuid, SerializedEntity>, Vec<LoadError>), Error> {
+    let (scene_entity_path, is_prefab) = path(scene);
     if is_prefab {
-        let prefab: Prefab = load_serialized_file(&scene_entity_path)?;
-        Ok(prefab.members)
-    } else {
-        load_serialized_file(&scene_entity_path)
+        if let Some(prefab) = load_fresh_cooked_file::<Prefab>(&scene_entity_path, &[&scene_entity_path]) {
+            return Ok((prefab.members, vec![]));
+        }
+    } else if let Some(entities) = load_fresh_cooked_file(&scene_entity_path, &[&scene_entity_path]) {
+        return Ok((entities, vec![]));
     }
+
+    let entity_file = EntityFile::load(&scene_entity_path, schema_kind(is_prefab))?;
+    Ok((entity_file.entities, entity_file.errors))
 }
 
-pub fn commit_all_entities(entities: &HashMap<Uuid, SerializedEntity>) -> AnyResult<()> {
-    let (path, is_prefab) = path();
-    if is_prefab {
-        let mut prefab: Prefab = load_serialized_file(&path)?;
-        prefab.members = entities.clone();
+/// Quarantined Entities stay in the file.
+pub fn commit_all_entities(scene: &Scene, entities: &HashMap<Uuid, SerializedEntity>) -> AnyResult<()> {
+    let (path, is_prefab) = path(scene);
+    let mut entity_file = EntityFile::load(&path, schema_kind(is_prefab))?;
+    entity_file.entities = entities.clone();
+
+    entity_file.save()
+}
 
-        save_serialized_file(&prefab, &path)
+fn schema_kind(is_prefab: bool) -> SchemaKind {
+    if is_prefab {
+        SchemaKind::Prefab
     } else {
-        save_serialized_file(entities, &path)
+        SchemaKind::Scene
     }
 }
 
 pub fn process_serialized_command(
+    scene: &Scene,
     command: EntitySerializationCommand,
     component_database: &mut ComponentDatabase,
     singleton_database: &mut SingletonDatabase,
     entities: &mut Vec<Entity>,
     entity_allocator: &mut EntityAllocator,
+    scene_graph: &mut SceneGraph,
     resources: &ResourcesDatabase,
 ) -> Result<(), Error> {
     match &command.command_type {
         EntitySerializationCommandType::Revert => {
-            let serialized_entity = load_entity_by_id(&command.id)?.ok_or_else(|| {
+            let serialized_entity = load_entity_by_id(scene, &command.id)?.ok_or_else(|| {
                 format_err!(
                     "We couldn't find {}. Is it in the YAML?",
                     Name::get_name_quick(&component_database.names, &command.entity)
@@ -60,7 +77,7 @@ pub fn process_serialized_command(
             );
 
             if let Some(post) = post {
-                component_database.post_deserialization(post, |component_list, sl| {
+                component_database.post_deserialization(post, scene_graph, |component_list, sl| {
                     if let Some((inner, _)) = component_list.get_mut(&command.entity) {
                         inner.post_deserialization(command.entity, sl);
                     }
@@ -70,6 +87,7 @@ pub fn process_serialized_command(
 
         EntitySerializationCommandType::Overwrite => {
             let result = serialize_entity_full(
+                scene,
                 &command.entity,
                 command.id,
                 component_database,
@@ -86,7 +104,7 @@ pub fn process_serialized_command(
         }
 
         EntitySerializationCommandType::StopSerializing => {
-            let result = unserialize_entity(&command.id)?;
+            let result = unserialize_entity(scene, &command.id)?;
             if result == false {
                 bail!(
                     "We couldn't find {}. Is it in the YAML?",
@@ -100,12 +118,13 @@ pub fn process_serialized_command(
 }
 
 pub fn serialize_all_entities(
+    scene: &Scene,
     entities: &[Entity],
     component_database: &ComponentDatabase,
     singleton_database: &SingletonDatabase,
     resources: &ResourcesDatabase,
 ) -> Result<(), Error> {
-    let mut serialized_entities = load_all_entities()?;
+    let mut serialized_entities = load_all_entities(scene)?;
 
     // FIND THE OLD SERIALIZED ENTITY
     for entity in entities {
@@ -122,12 +141,13 @@ pub fn serialize_all_entities(
         }
     }
 
-    commit_all_entities(&serialized_entities)
+    commit_all_entities(scene, &serialized_entities)
 }
 
 /// This serializes an entity. It is "full" because of its parameters taken -- it serializes over the
 /// entire entity, essentially creating a new Serialized Entity and then comitting that to the scene.
 pub fn serialize_entity_full(
+    scene: &Scene,
     entity_id: &Entity,
     serialized_id: uuid::Uuid,
     component_database: &ComponentDatabase,
@@ -141,7 +161,7 @@ pub fn serialize_entity_full(
         singleton_database,
         resources,
     ) {
-        match commit_entity_to_serialized_scene(se) {
+        match commit_entity_to_serialized_scene(scene, se) {
             Ok(()) => true,
             Err(e) => {
                 error!("COULDN'T SERIALIZE! {}", e);
@@ -154,30 +174,34 @@ pub fn serialize_entity_full(
 }
 
 // @techdebt Use it or lose it!
-pub fn unserialize_entity(serialized_id: &uuid::Uuid) -> Result<bool, Error> {
-    let mut entities = load_all_entities()?;
+pub fn unserialize_entity(scene: &Scene, serialized_id: &uuid::Uuid) -> Result<bool, Error> {
+    let mut entities = load_all_entities(scene)?;
 
     // FIND THE OLD PREFAB
     let succeeded = entities.remove(serialized_id).is_some();
-    commit_all_entities(&entities)?;
+    commit_all_entities(scene, &entities)?;
 
     Ok(succeeded)
 }
 
-pub fn commit_entity_to_serialized_scene(serialized_entity: SerializedEntity) -> Result<(), Error> {
-    let mut entities = load_all_entities()?;
+pub fn commit_entity_to_serialized_scene(
+    scene: &Scene,
+    serialized_entity: SerializedEntity,
+) -> Result<(), Error> {
+    let mut entities = load_all_entities(scene)?;
     entities.insert(serialized_entity.id, serialized_entity);
 
-    commit_all_entities(&entities)
+    commit_all_entities(scene, &entities)
 }
 
 pub fn load_committed_entity(
+    scene: &Scene,
     serialized_data: &SerializationMarker,
 ) -> Result<Option<SerializedEntity>, Error> {
-    load_entity_by_id(&serialized_data.id)
+    load_entity_by_id(scene, &serialized_data.id)
 }
 
-pub fn load_entity_by_id(id: &uuid::Uuid) -> Result<Option<SerializedEntity>, Error> {
-    let mut entities: HashMap<Uuid, SerializedEntity> = load_all_entities()?;
+pub fn load_entity_by_id(scene: &Scene, id: &uuid::Uuid) -> Result<Option<SerializedEntity>, Error> {
+    let mut entities: HashMap<Uuid, SerializedEntity> = load_all_entities(scene)?;
     Ok(entities.remove(id))
 }
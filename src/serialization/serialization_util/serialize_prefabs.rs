@@ -1,10 +1,32 @@
 use super::*;
 use uuid::Uuid;
 
+/// The Entities in one Scene or Prefab file which directly inherit a Prefab member, and
+/// override the Component, or field, being applied to it.
+#[derive(Debug, Clone)]
+pub struct OverridingFile {
+    pub path: String,
+    pub kind: SchemaKind,
+    /// Each Entity's Uuid, and its Name if it has one.
+    pub entities: Vec<(Uuid, Option<String>)>,
+}
+
+/// What applying a Component, or a field in one, to a Prefab member would change on disk.
+#[derive(Debug, Clone, Default)]
+pub struct PropagationReport {
+    pub overriding_files: Vec<OverridingFile>,
+    /// Files we couldn't load, so couldn't check. Why, for each.
+    pub unchecked_files: Vec<String>,
+}
+
 lazy_static::lazy_static! {
     static ref PREFAB_GLOB: String = format!("{}/**/*.prefab", PREFAB_DIRECTORY);
 }
 
+pub fn prefab_paths() -> AnyResult<Vec<std::path::PathBuf>> {
+    Ok(glob::glob(&PREFAB_GLOB)?.collect::<Result<_, _>>()?)
+}
+
 fn path(entity_id: &str) -> String {
     format!("{}/{}.prefab", PREFAB_DIRECTORY, entity_id)
 }
@@ -23,22 +45,31 @@ pub fn cycle_prefab(prefab: Prefab) -> Result<Prefab, Error> {
     Ok(serde_yaml::from_value(serde_yaml::to_value(prefab)?)?)
 }
 
+/// If the Prefab's file has quarantined members, they stay in the file.
 pub fn serialize_prefab(prefab: &Prefab) -> Result<(), Error> {
     let path = path(&prefab.root_id().to_string());
+    let mut prefab_file = if std::path::Path::new(&path).exists() {
+        EntityFile::load(&path, SchemaKind::Prefab)?
+    } else {
+        EntityFile::new(&path, SchemaKind::Prefab)
+    };
 
-    save_serialized_file(&prefab, &path)
+    prefab_file.set_prefab(prefab)?;
+    prefab_file.save()
 }
 
 pub fn invalidate_prefab(prefab: &Prefab) -> AnyResult<()> {
     let path = path(&prefab.root_id().to_string());
     fs::remove_file(&path)?;
 
-    save_serialized_file(prefab, &invalid_path(&prefab.root_id().to_string()))
+    let mut prefab_file = EntityFile::new(&invalid_path(&prefab.root_id().to_string()), SchemaKind::Prefab);
+    prefab_file.set_prefab(prefab)?;
+    prefab_file.save()
 }
 
 pub fn load_prefab(prefab_id: &Uuid) -> Result<Option<Prefab>, Error> {
     // ENTITIES
-    let prefab: Result<Prefab, _> = load_serialized_file(&path(&prefab_id.to_string()));
+    let prefab = load_prefab_file(&path(&prefab_id.to_string()));
 
     Ok(prefab
         .map_err(|e| error!("Error loading Prefab File: {}", e))
@@ -54,9 +85,142 @@ pub fn load_all_prefabs() -> AnyResult<PrefabMap> {
 
     for path in glob::glob(&PREFAB_GLOB)? {
         let path = path?;
-        let prefab: Prefab = load_serialized_file(path.to_str().unwrap())?;
+        let prefab = load_prefab_file(path.to_str().unwrap())?;
         ret.insert(prefab.root_id(), prefab);
     }
 
     Ok(ret)
 }
+
+/// Prefers the cooked copy. Any members we have to quarantine get logged.
+pub fn load_prefab_file(path: &str) -> AnyResult<Prefab> {
+    if let Some(prefab) = load_fresh_cooked_file(path, &[path]) {
+        return Ok(prefab);
+    }
+
+    let prefab_file = EntityFile::load(path, SchemaKind::Prefab)?;
+    for load_error in prefab_file.errors.iter() {
+        error!("Quarantined a Prefab member. {}", load_error);
+    }
+
+    prefab_file.prefab()
+}
+
+/// Checks every Scene and Prefab on disk for Entities which directly inherit `prefab_marker`
+/// and override it at `path`, which is a Component's key or a field in it. This doesn't
+/// write anything, so it's the dry run of `drop_overrides`.
+pub fn find_overriding_inheritors(prefab_marker: &PrefabMarker, path: &str) -> AnyResult<PropagationReport> {
+    let mut report = PropagationReport::default();
+    for (path, kind) in entity_files()? {
+        let entity_file = match EntityFile::load(&path, kind) {
+            Ok(entity_file) => entity_file,
+            Err(e) => {
+                report.unchecked_files.push(e.to_string());
+                continue;
+            }
+        };
+
+        let mut entities: Vec<(Uuid, Option<String>)> = entity_file
+            .entities
+            .values()
+            .filter(|se| overrides_inherited(se, prefab_marker, path))
+            .map(|se| (se.id, se.name.as_ref().map(|name| name.inner.name.clone())))
+            .collect();
+
+        if entities.is_empty() == false {
+            entities.sort_by_key(|(id, _)| *id);
+            report
+                .overriding_files
+                .push(OverridingFile { path, kind, entities });
+        }
+    }
+
+    Ok(report)
+}
+
+/// What `drop_overrides` changed on disk, and what it couldn't.
+#[derive(Default)]
+pub struct DroppedOverrides {
+    /// For the caller to cache.
+    pub changed_prefabs: Vec<Prefab>,
+    pub written_files: Vec<String>,
+    /// Files we couldn't change. Why, for each.
+    pub failed_files: Vec<String>,
+}
+
+/// Takes the overrides in the report out of their files, so those Entities get the Prefab's
+/// value instead. `base` is the Prefab member they inherit, as it is now. We load each file
+/// again first, in case it changed since the dry run. A file which fails doesn't stop the
+/// rest, since by now the Prefab has changed anyway.
+pub fn drop_overrides(
+    report: &PropagationReport,
+    prefab_marker: &PrefabMarker,
+    base: &SerializedEntity,
+    path: &str,
+) -> DroppedOverrides {
+    let mut dropped_overrides = DroppedOverrides::default();
+
+    for overriding_file in report.overriding_files.iter() {
+        let entity_file = match drop_overrides_in_file(overriding_file, prefab_marker, base, path) {
+            Ok(Some(entity_file)) => entity_file,
+            Ok(None) => continue,
+            Err(e) => {
+                dropped_overrides
+                    .failed_files
+                    .push(format!("{}: {}", overriding_file.path, e));
+                continue;
+            }
+        };
+        dropped_overrides.written_files.push(overriding_file.path.clone());
+
+        if overriding_file.kind == SchemaKind::Prefab {
+            match entity_file.prefab() {
+                Ok(prefab) => dropped_overrides.changed_prefabs.push(prefab),
+                Err(e) => dropped_overrides
+                    .failed_files
+                    .push(format!("{}: {}", overriding_file.path, e)),
+            }
+        }
+    }
+
+    dropped_overrides
+}
+
+/// Gives back the file if we changed and saved it.
+fn drop_overrides_in_file(
+    overriding_file: &OverridingFile,
+    prefab_marker: &PrefabMarker,
+    base: &SerializedEntity,
+    path: &str,
+) -> AnyResult<Option<EntityFile>> {
+    let mut entity_file = EntityFile::load(&overriding_file.path, overriding_file.kind)?;
+    let mut changed = false;
+
+    for (id, _) in overriding_file.entities.iter() {
+        if let Some(serialized_entity) = entity_file.entities.get_mut(id) {
+            if overrides_inherited(serialized_entity, prefab_marker, path) {
+                prefab_system::drop_override(serialized_entity, base, path)?;
+                changed = true;
+            }
+        }
+    }
+
+    if changed == false {
+        return Ok(None);
+    }
+
+    entity_file.save()?;
+    Ok(Some(entity_file))
+}
+
+fn overrides_inherited(
+    serialized_entity: &SerializedEntity,
+    prefab_marker: &PrefabMarker,
+    path: &str,
+) -> bool {
+    serialized_entity
+        .prefab_marker
+        .as_ref()
+        .map_or(false, |pm| &pm.inner == prefab_marker)
+        && prefab_system::overrides_path(serialized_entity, path)
+}
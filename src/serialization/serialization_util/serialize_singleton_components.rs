@@ -1,13 +1,74 @@
 use super::*;
+use serde_yaml::Value as YamlValue;
 
-pub fn path() -> String {
-    scene_system::CURRENT_SCENE.lock().unwrap().singleton_path()
+pub fn path(scene: &Scene) -> String {
+    scene.singleton_path()
 }
 
-pub fn load_singleton_database() -> Result<SingletonDatabase, Error> {
-    load_serialized_file(&path())
+pub fn default_path() -> String {
+    format!("{}/{}", PREFAB_DIRECTORY, DEFAULT_SINGLETONS_SUBPATH)
 }
 
-pub fn serialize_singleton_database(singleton_database: &SingletonDatabase) -> Result<(), Error> {
-    save_serialized_file(singleton_database, &path())
+/// Prefers the cooked copy, if neither the Scene's file nor the defaults have changed
+/// since it was cooked.
+pub fn load_singleton_database(scene: &Scene) -> Result<SingletonDatabase, Error> {
+    let path = path(scene);
+    match load_fresh_cooked_file(&path, &[&path, &default_path()]) {
+        Some(singleton_database) => Ok(singleton_database),
+        None => load_serialized_singleton_database(scene),
+    }
+}
+
+/// Any singleton the Scene's file is missing comes from `default_singleton_data.yaml`,
+/// and if that's missing it too, from its `Default`.
+pub fn load_serialized_singleton_database(scene: &Scene) -> Result<SingletonDatabase, Error> {
+    let path = path(scene);
+    let mut singletons = load_versioned_value(&path, SchemaKind::Singletons)?;
+    fill_missing_singletons(
+        &mut singletons,
+        load_versioned_value(&default_path(), SchemaKind::Singletons)?,
+    );
+    fill_missing_singletons(
+        &mut singletons,
+        serde_yaml::to_value(SingletonDatabase::default())?,
+    );
+
+    Ok(serde_yaml::from_value(singletons).map_err(|e| LoadError::new(&path, e))?)
+}
+
+/// The singletons new Scenes start with.
+pub fn load_default_singleton_database() -> Result<SingletonDatabase, Error> {
+    let mut singletons = load_versioned_value(&default_path(), SchemaKind::Singletons)?;
+    fill_missing_singletons(
+        &mut singletons,
+        serde_yaml::to_value(SingletonDatabase::default())?,
+    );
+
+    Ok(serde_yaml::from_value(singletons).map_err(|e| LoadError::new(&default_path(), e))?)
+}
+
+pub fn serialize_singleton_database(
+    scene: &Scene,
+    singleton_database: &SingletonDatabase,
+) -> Result<(), Error> {
+    save_versioned_file(singleton_database, &path(scene))
+}
+
+fn fill_missing_singletons(singletons: &mut YamlValue, defaults: YamlValue) {
+    let defaults = match defaults {
+        YamlValue::Mapping(defaults) => defaults,
+        _ => return,
+    };
+
+    match singletons {
+        YamlValue::Mapping(singletons) => {
+            for (name, singleton) in defaults {
+                if singletons.contains_key(&name) == false {
+                    singletons.insert(name, singleton);
+                }
+            }
+        }
+        // An empty file
+        _ => *singletons = YamlValue::Mapping(defaults),
+    }
 }
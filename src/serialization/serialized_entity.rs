@@ -1,8 +1,8 @@
 use super::{
-    physics_components::*, prefab_system, ComponentBounds, ComponentDatabase, ConversantNPC, DrawRectangle,
-    Entity, Follow, GraphNode, GridObject, Marker, Name, NonInspectableEntities, Player, PrefabMarker,
-    ResourcesDatabase, SceneSwitcher, SerializableComponent, SingletonDatabase, SoundSource, Sprite,
-    TextSource, Transform, Velocity,
+    imgui_component_utils::SyncStatus, physics_components, prefab_system, ComponentBounds, ComponentDatabase,
+    ConversantNPC, DrawRectangle, Entity, Follow, GraphNode, GridObject, Name, NonInspectableEntities,
+    Player, PrefabMarker, ResourcesDatabase, Scene, SceneSwitcher, SerializableComponent, SingletonDatabase,
+    SoundSource, Sprite, Tags, TextSource, Transform, Velocity,
 };
 use serde_yaml::Value as YamlValue;
 use uuid::Uuid;
@@ -50,32 +50,53 @@ impl<T: Default> SerializedComponentExtenstions<T> for SerializedComponentWrappe
     }
 }
 
-// This should mirror ComponentDatabse
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
-#[serde(default)]
-pub struct SerializedEntity {
-    pub name: SerializedComponentWrapper<Name>,
-    pub player: SerializedComponentWrapper<Player>,
-    pub transform: SerializedComponentWrapper<Transform>,
-    pub grid_object: SerializedComponentWrapper<GridObject>,
-    pub scene_switcher: SerializedComponentWrapper<SceneSwitcher>,
-    pub graph_node: SerializedComponentWrapper<GraphNode>,
-    pub velocity: SerializedComponentWrapper<Velocity>,
-    pub sprite: SerializedComponentWrapper<Sprite>,
-    pub sound_source: SerializedComponentWrapper<SoundSource>,
-    pub draw_rectangle: SerializedComponentWrapper<DrawRectangle>,
-    pub bounding_box: SerializedComponentWrapper<BoundingBox>,
-    pub text_source: SerializedComponentWrapper<TextSource>,
-    // pub tilemap: SerializedComponentWrapper<TilemapSerialized>,
-    pub follow: SerializedComponentWrapper<Follow>,
-    pub conversant_npc: SerializedComponentWrapper<ConversantNPC>,
-    pub prefab_marker: SerializedComponentWrapper<PrefabMarker>,
-
-    pub id: Uuid,
-    pub marker: Option<Marker>,
-}
+component_registry!(impl_serialized_entity);
 
 impl SerializedEntity {
+    /// How the Entity compares to what's on disk, for the editor. We only serialize the
+    /// Entity again if its SerializationMarker forgot the last answer, or it moved.
+    pub fn serialization_status(
+        entity_id: &Entity,
+        scene: &Scene,
+        component_database: &mut ComponentDatabase,
+        singleton_database: &SingletonDatabase,
+        resources: &ResourcesDatabase,
+    ) -> SyncStatus {
+        let moved = component_database
+            .transforms
+            .get(entity_id)
+            .map_or(false, |transform_c| transform_c.inner().changed());
+
+        let serialization_id = match component_database.serialization_markers.get_mut(entity_id) {
+            Some(serialization_marker) => {
+                let serialization_marker = serialization_marker.inner_mut();
+                if moved == false {
+                    if let Some(sync_status) = serialization_marker.cached_serialization_status(scene) {
+                        return sync_status;
+                    }
+                }
+
+                serialization_marker.id
+            }
+            None => return SyncStatus::default(),
+        };
+
+        let current_serialized_entity = SerializedEntity::new(
+            entity_id,
+            serialization_id,
+            component_database,
+            singleton_database,
+            resources,
+        );
+
+        component_database
+            .serialization_markers
+            .get_mut(entity_id)
+            .unwrap()
+            .inner_mut()
+            .get_serialization_status(scene, current_serialized_entity.as_ref())
+    }
+
     pub fn new(
         entity_id: &Entity,
         serialization_id: Uuid,
@@ -97,6 +118,8 @@ impl SerializedEntity {
         if let Some(prefab) = prefab {
             serialized_entity
                 .foreach_component_dedup(|component, active| component.is_serialized(&prefab, *active));
+            serialized_entity.prefab_overrides =
+                prefab_system::find_prefab_overrides(&serialized_entity, &prefab);
         }
 
         Some(serialized_entity)
@@ -162,129 +185,6 @@ impl SerializedEntity {
         }
     }
 
-    pub fn foreach_component(
-        &mut self,
-        entity_bitmask: NonInspectableEntities,
-        mut f: impl FnMut(&dyn ComponentBounds, &bool),
-        f_util: Option<impl FnMut(&mut Uuid, &mut Option<Marker>)>,
-    ) {
-        let SerializedEntity {
-            name,
-            player,
-            transform,
-            grid_object,
-            scene_switcher,
-            graph_node,
-            velocity,
-            sprite,
-            sound_source,
-            draw_rectangle,
-            bounding_box,
-            text_source,
-            // tilemap,
-            follow,
-            conversant_npc,
-            prefab_marker,
-            id,
-            marker,
-        } = self;
-
-        macro_rules! do_action {
-            ( $( $x:ident ),* ) => {
-                $(
-                    if let Some(serialized_component) = $x {
-                        f(&serialized_component.inner, &serialized_component.active);
-                    }
-                )*
-            };
-        }
-
-        if entity_bitmask.contains(NonInspectableEntities::NAME) {
-            do_action!(name);
-        }
-
-        do_action!(
-            player,
-            transform,
-            grid_object,
-            scene_switcher,
-            velocity,
-            sprite,
-            sound_source,
-            draw_rectangle,
-            bounding_box,
-            text_source,
-            // tilemap,
-            follow,
-            conversant_npc
-        );
-
-        if entity_bitmask.contains(NonInspectableEntities::GRAPH_NODE) {
-            do_action!(graph_node);
-        }
-
-        if entity_bitmask.contains(NonInspectableEntities::PREFAB) {
-            do_action!(prefab_marker);
-        }
-
-        if let Some(mut f_util) = f_util {
-            f_util(id, marker);
-        }
-    }
-
-    pub fn foreach_component_dedup(&mut self, mut f: impl FnMut(&dyn ComponentBounds, &bool) -> bool) {
-        let SerializedEntity {
-            name,
-            player,
-            transform,
-            grid_object,
-            scene_switcher,
-            graph_node,
-            velocity,
-            sprite,
-            sound_source,
-            draw_rectangle,
-            bounding_box,
-            text_source,
-            // tilemap,
-            follow,
-            conversant_npc,
-            prefab_marker: _,
-            id: _,
-            marker: _,
-        } = self;
-
-        macro_rules! dedup_serialization {
-            ( $( $x:ident ),* ) => {
-                $(
-                    if let Some(serialized_component) = $x {
-                        if f(&serialized_component.inner, &serialized_component.active) {
-                            *$x = None;
-                        }
-                    }
-                )*
-            };
-        }
-
-        dedup_serialization!(
-            name,
-            player,
-            transform,
-            grid_object,
-            scene_switcher,
-            graph_node,
-            velocity,
-            sprite,
-            sound_source,
-            draw_rectangle,
-            bounding_box,
-            text_source,
-            // tilemap,
-            follow,
-            conversant_npc
-        );
-    }
-
     pub fn log_to_console(&self) {
         println!("---");
         println!("Serialized Entity: {:#?}", self);
@@ -0,0 +1,117 @@
+use super::{systems::grid_system::Grid, Ecs, Entity, EntityAllocator, Name};
+
+/// A handle to an Entity which isn't alive anymore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleHandle {
+    /// The Entity whose Component is holding the handle, if a Component is holding it.
+    pub holder: Option<Entity>,
+    /// Where the handle lives, like `Follow.target`.
+    pub location: &'static str,
+    pub target: Entity,
+    /// True if we forgot the handle after finding it.
+    pub cleared: bool,
+}
+
+/// Looks for every handle in the world which points at a dead Entity, and logs them. If
+/// `clear` is true, we also forget them. Transform parents are only reported, since
+/// taking a Transform out of the Scene Graph would touch its dead parent.
+pub fn validate_entity_handles(ecs: &mut Ecs, grid: &mut Grid, clear: bool) -> Vec<StaleHandle> {
+    let allocator = &ecs.entity_allocator;
+    let component_database = &mut ecs.component_database;
+    let mut stale_handles = vec![];
+
+    // Returns true if the target is alive.
+    let mut check = |holder: Option<Entity>, location: &'static str, target: Entity, cleared: bool| {
+        let alive = allocator.is_live(target);
+        if alive == false {
+            stale_handles.push(StaleHandle {
+                holder,
+                location,
+                target,
+                cleared,
+            });
+        }
+        alive
+    };
+
+    for follow_c in component_database.follows.iter_mut() {
+        let holder = follow_c.entity_id();
+        let follow = follow_c.inner_mut();
+        if let Some(target) = follow.target.target {
+            if check(Some(holder), "Follow.target", target, clear) == false && clear {
+                follow.target.target = None;
+            }
+        }
+    }
+
+    for conversant_c in component_database.conversant_npcs.iter_mut() {
+        let holder = conversant_c.entity_id();
+        let conversant = conversant_c.inner_mut();
+        if let Some(target) = conversant.conversation_partner.target {
+            if check(Some(holder), "ConversantNPC.conversation_partner", target, clear) == false && clear {
+                conversant.conversation_partner.target = None;
+            }
+        }
+        if let Some(target) = conversant.runtime_ui {
+            if check(Some(holder), "ConversantNPC.runtime_ui", target, clear) == false && clear {
+                conversant.runtime_ui = None;
+            }
+        }
+    }
+
+    for node_c in component_database.graph_nodes.iter_mut() {
+        let holder = node_c.entity_id();
+        if let Some(children) = &mut node_c.inner_mut().children {
+            children.retain(|child| match child.target {
+                Some(target) => check(Some(holder), "GraphNode.children", target, clear) || clear == false,
+                None => true,
+            });
+        }
+    }
+
+    for transform_c in component_database.transforms.iter() {
+        if let Some(parent) = transform_c.inner().parent_entity() {
+            check(Some(transform_c.entity_id()), "Transform.parent", parent, false);
+        }
+    }
+
+    for row in 0..grid.num_rows() {
+        for column in 0..grid.num_columns() {
+            if let Some(target) = grid[(row, column)] {
+                if check(None, "Grid", target, clear) == false && clear {
+                    grid[(row, column)] = None;
+                }
+            }
+        }
+    }
+
+    ecs.singleton_database.associated_entities.retain(|_, target| {
+        check(None, "SingletonDatabase.associated_entities", *target, clear) || clear == false
+    });
+
+    for stale_handle in stale_handles.iter() {
+        report(stale_handle, ecs);
+    }
+
+    stale_handles
+}
+
+/// Forgets every selected Entity which isn't alive anymore.
+pub fn validate_selection(selection: &mut std::collections::HashSet<Entity>, allocator: &EntityAllocator) {
+    selection.retain(|entity| allocator.is_live(*entity));
+}
+
+fn report(stale_handle: &StaleHandle, ecs: &Ecs) {
+    let holder = match stale_handle.holder {
+        Some(holder) => Name::get_name_quick(&ecs.component_database.names, &holder),
+        None => "The Scene".to_string(),
+    };
+
+    error!(
+        "{}'s {} points at {}, which was despawned.{}",
+        holder,
+        stale_handle.location,
+        stale_handle.target,
+        if stale_handle.cleared { " Cleared it." } else { "" }
+    );
+}
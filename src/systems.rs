@@ -3,6 +3,7 @@ pub use super::*;
 pub mod cross_cutting_system;
 pub mod follow_system;
 pub mod grid_system;
+pub mod hot_reload_system;
 pub mod imgui_system;
 pub mod input_system;
 pub mod physics_system;
@@ -10,6 +11,7 @@ pub mod player_system;
 pub mod prefab_system;
 pub mod renderer_system;
 pub mod scene_system;
+pub mod scheduler;
 pub mod singleton_systems;
 pub mod sound_system;
 pub mod sprite_system;
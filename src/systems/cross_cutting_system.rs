@@ -1,42 +1,15 @@
-use super::{
-    physics_components::BoundingBox, ComponentList, DrawRectangle, Ecs, Rect, ResourcesDatabase, Sprite,
-};
-
-pub fn cross_cutting_system(ecs: &mut Ecs, resources: &ResourcesDatabase) {
-    bounding_box_and_sprite(
-        &mut ecs.component_database.bounding_boxes,
-        &ecs.component_database.sprites,
-        resources,
-    );
+use super::{physics_components::BoundingBox, query, ComponentList, DrawRectangle, Ecs, Transform};
 
+pub fn cross_cutting_system(ecs: &mut Ecs) {
     draw_rectangle_and_bounding_box(
         &mut ecs.component_database.draw_rectangles,
         &ecs.component_database.bounding_boxes,
     );
-}
 
-fn bounding_box_and_sprite(
-    bbs: &mut ComponentList<BoundingBox>,
-    sprites: &ComponentList<Sprite>,
-    resources: &ResourcesDatabase,
-) {
-    for this_bb in bbs.iter_mut() {
-        let this_entity_id = this_bb.entity_id();
-        let this_bounding_box: &mut BoundingBox = this_bb.inner_mut();
-
-        if this_bounding_box.bind_to_sprite {
-            if let Some(this_sprite) = sprites.get(&this_entity_id) {
-                if let Some(sprite_name) = &this_sprite.inner().sprite_name {
-                    let sprite_data = resources.sprites.get(sprite_name).unwrap();
-                    let rel_location = sprite_data.origin.sprite_location_relative(sprite_data.size);
-
-                    this_bounding_box.rect = Rect::point_width(rel_location, sprite_data.size.into());
-                };
-            } else {
-                this_bounding_box.bind_to_sprite = false;
-            }
-        }
-    }
+    bounding_box_and_transform(
+        &mut ecs.component_database.bounding_boxes,
+        &ecs.component_database.transforms,
+    );
 }
 
 fn draw_rectangle_and_bounding_box(
@@ -56,3 +29,12 @@ fn draw_rectangle_and_bounding_box(
         }
     }
 }
+
+// Most things stand still, so we only move the Bounding Boxes whose Transform changed.
+fn bounding_box_and_transform(bbs: &mut ComponentList<BoundingBox>, transforms: &ComponentList<Transform>) {
+    for (bb_c, transform_c) in query((bbs, transforms)) {
+        let transform = transform_c.inner();
+        bb_c.inner_mut()
+            .sync_world_rect(&transform.world_transform(), transform.changed());
+    }
+}
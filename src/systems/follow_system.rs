@@ -1,25 +1,32 @@
-use super::{Approach, ComponentList, Follow, Name, Transform, Vec2};
+use super::{Approach, ComponentList, EntityAllocator, Follow, Name, Transform, Vec2};
 use log_once::error_once;
 
 pub fn update_follows(
-    afs: &mut ComponentList<Follow>,
+    afs: &ComponentList<Follow>,
     transforms: &mut ComponentList<Transform>,
     names: &ComponentList<Name>,
+    entity_allocator: &EntityAllocator,
     delta_time: f32,
 ) {
-    for follow_c in afs.iter_mut() {
+    for follow_c in afs.iter() {
         let id = follow_c.entity_id();
-        if let Some(target) = &follow_c.inner_mut().target.target {
+        if let Some(target) = &follow_c.inner().target.target {
+            // Our target was despawned, so there's nothing to follow anymore.
+            if entity_allocator.is_live(*target) == false {
+                continue;
+            }
+
             if let Some(target_position) = transforms.get(&target).map(|tc| tc.inner().world_position()) {
                 let target_position = target_position + follow_c.inner().offset;
 
                 // Transform Components
-                let our_transform_c = transforms.get_mut_or_default(&follow_c.entity_id());
-                let transform_pos: &mut Vec2 = &mut our_transform_c.inner_mut().world_position();
+                let our_transform_c = transforms.get_mut_or_default(&id);
+                let current_position = our_transform_c.inner().world_position();
+                let mut transform_pos = current_position;
 
                 match follow_c.inner().approach {
                     Approach::Instant => {
-                        *transform_pos = target_position;
+                        transform_pos = target_position;
                     }
 
                     Approach::Linear(speed) => {
@@ -28,13 +35,16 @@ pub fn update_follows(
 
                     Approach::Asymptotic(weight) => {
                         transform_pos.asymptotic_moved(target_position, weight * delta_time);
-                        if (*transform_pos - target_position).magnitude_squared() < delta_time {
-                            *transform_pos = target_position;
+                        if (transform_pos - target_position).magnitude_squared() < delta_time {
+                            transform_pos = target_position;
                         }
                     }
                 };
 
-            // Move
+                // Move
+                our_transform_c
+                    .inner_mut()
+                    .edit_local_position(|local_position| local_position + transform_pos - current_position);
             } else {
                 error_once!(
                     "{} couldn't find a transform on {} for its Asymptote follow. We need it to have a transform on it!",
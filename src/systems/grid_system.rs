@@ -1,6 +1,6 @@
 use super::{
-    cardinals::CardinalPrime, scene_system, Component, ComponentList, Ecs, Entity, GridObject, GridType,
-    Marker, Name, Transform, Vec2,
+    cardinals::CardinalPrime, query, BoxPushed, Component, ComponentList, Ecs, Entity, EntityMoved,
+    EventChannel, GridObject, GridType, LevelSolved, Marker, Name, Transform, Vec2,
 };
 use array2d::Array2D;
 
@@ -11,24 +11,38 @@ pub const GRID_DIMENSIONS_MAX_F32: (f32, f32) =
 pub type Grid = Array2D<Option<Entity>>;
 
 pub fn update_grid_positions(ecs: &mut Ecs, grid: &mut Grid) {
+    let component_database = &mut ecs.component_database;
+    let entity_moved = &mut ecs.events.entity_moved;
+
+    // Anything which moved without going through the Grid (like in the editor) gets
+    // registered again wherever it is now.
+    for transform_c in query(&component_database.transforms).with(&component_database.grid_objects) {
+        if transform_c.inner().changed() {
+            let id = transform_c.entity_id();
+            unregister_entity(grid, &id);
+            register_entity(grid, id, transform_c.inner().world_position(), None);
+        }
+    }
+
     // ImGui Movement
-    for grid_object_c in ecs.component_database.grid_objects.iter_mut() {
+    for (grid_object_c, transform_c) in query((
+        &mut component_database.grid_objects,
+        &mut component_database.transforms,
+    )) {
         let id = grid_object_c.entity_id();
         let grid_object: &mut GridObject = grid_object_c.inner_mut();
 
         if grid_object.move_to_point {
-            if let Some(transform) = ecs.component_database.transforms.get_mut(&id) {
-                let current_position = world_to_grid_position(transform.inner().world_position());
-                let desired_position: (usize, usize) = (
-                    grid_object.move_to_point_pos.x as usize,
-                    grid_object.move_to_point_pos.y as usize,
-                );
-
-                if desired_position.0 >= GRID_DIMENSIONS.0 || desired_position.1 >= GRID_DIMENSIONS.1 {
-                    error!("Couldn't move! Attempting to move to far!")
-                } else {
-                    move_entity(transform, grid, desired_position, current_position);
-                }
+            let current_position = world_to_grid_position(transform_c.inner().world_position());
+            let desired_position: (usize, usize) = (
+                grid_object.move_to_point_pos.x as usize,
+                grid_object.move_to_point_pos.y as usize,
+            );
+
+            if desired_position.0 >= GRID_DIMENSIONS.0 || desired_position.1 >= GRID_DIMENSIONS.1 {
+                error!("Couldn't move! Attempting to move to far!")
+            } else {
+                move_entity(transform_c, grid, desired_position, current_position, entity_moved);
             }
 
             // Reset the Grid Object Move to Point
@@ -37,40 +51,35 @@ pub fn update_grid_positions(ecs: &mut Ecs, grid: &mut Grid) {
         }
 
         if grid_object.register {
-            if let Some(transform) = ecs.component_database.transforms.get(&id) {
-                register_entity(grid, id, transform.inner().world_position(), None);
-            }
-
+            register_entity(grid, id, transform_c.inner().world_position(), None);
             grid_object.register = false;
         }
     }
 
-    // Player Movement
-    let mut attempted_moves = vec![];
-    for player in ecs.component_database.players.iter_mut() {
-        let entity_id = player.entity_id();
-
-        let movement: Option<CardinalPrime> = {
-            if let Some(vc) = ecs.component_database.velocities.get_mut(&entity_id) {
-                vc.inner_mut().intended_direction.take()
-            } else {
-                None
-            }
-        };
-        let current_position = ecs
-            .component_database
-            .transforms
-            .get_mut(&entity_id)
-            .map(|tc| world_to_grid_position(tc.inner_mut().world_position()));
+    // Without a Transform, there's nowhere to move or register to, so we just drop those.
+    for grid_object_c in query(&mut component_database.grid_objects).without(&component_database.transforms) {
+        let grid_object: &mut GridObject = grid_object_c.inner_mut();
 
-        if let Some(movement) = movement {
-            if let Some(current_position) = current_position {
-                attempted_moves.push((entity_id, movement, current_position));
-            }
-        }
+        grid_object.move_to_point = false;
+        grid_object.move_to_point_pos = Default::default();
+        grid_object.register = false;
     }
 
-    // We do this so we don't reallocate under ourselves!
+    // Player Movement
+    let attempted_moves: Vec<(Entity, CardinalPrime, (usize, usize))> = query((
+        &mut component_database.velocities,
+        &component_database.transforms,
+    ))
+    .with(&component_database.players)
+    .filter_map(|(velocity_c, transform_c)| {
+        let movement = velocity_c.inner_mut().intended_direction.take()?;
+        let current_position = world_to_grid_position(transform_c.inner().world_position());
+
+        Some((velocity_c.entity_id(), movement, current_position))
+    })
+    .collect();
+
+    // Moving can push other entities, so `attempt_to_move` needs the whole Ecs.
     for (entity_id, movement, current_position) in attempted_moves {
         if let Some(valid_next_position) = move_position(current_position, movement) {
             attempt_to_move(
@@ -132,17 +141,10 @@ fn attempt_to_move(
         match grid_type {
             GridType::Flag => {
                 if my_object_type == GridType::Player {
-                    if let Some(scene_switcher) = ecs.component_database.scene_switchers.get(&entity_in_grid)
-                    {
-                        if scene_system::set_next_scene(super::Scene::new(
-                            scene_switcher.inner().target_scene.clone(),
-                        )) == false
-                        {
-                            error!("Couldn't switch scenes! Does it exist?");
-                        };
-                    } else {
-                        error!("Flag wasn't a scene switcher? This is chaos!");
-                    }
+                    ecs.events.level_solved.send(LevelSolved {
+                        player: *entity_id,
+                        flag: entity_in_grid,
+                    });
                 }
             }
             GridType::Pushable => {
@@ -156,6 +158,14 @@ fn attempt_to_move(
                         ecs,
                         grid,
                     );
+
+                    if move_to_spot {
+                        ecs.events.box_pushed.send(BoxPushed {
+                            entity: entity_in_grid,
+                            pusher: *entity_id,
+                            direction: movement,
+                        });
+                    }
                 }
             }
             GridType::Blockable | GridType::Player => {
@@ -173,13 +183,19 @@ fn attempt_to_move(
             grid,
             new_position,
             current_position,
+            &mut ecs.events.entity_moved,
         );
     }
 
     move_to_spot
 }
 
-fn register_entity(grid: &mut Grid, entity: Entity, position: Vec2, _names: Option<&ComponentList<Name>>) {
+pub fn register_entity(
+    grid: &mut Grid,
+    entity: Entity,
+    position: Vec2,
+    _names: Option<&ComponentList<Name>>,
+) {
     if position.x >= GRID_DIMENSIONS_MIN_F32.0
         && position.x <= GRID_DIMENSIONS_MAX_F32.0
         && position.y >= GRID_DIMENSIONS_MIN_F32.0
@@ -189,11 +205,23 @@ fn register_entity(grid: &mut Grid, entity: Entity, position: Vec2, _names: Opti
     }
 }
 
+/// Removes the Entity from wherever it is in the Grid.
+pub fn unregister_entity(grid: &mut Grid, entity: &Entity) {
+    for row in 0..grid.num_rows() {
+        for column in 0..grid.num_columns() {
+            if grid[(row, column)] == Some(*entity) {
+                grid[(row, column)] = None;
+            }
+        }
+    }
+}
+
 fn move_entity(
     transform: &mut Component<Transform>,
     grid: &mut Grid,
     valid_next_position: (usize, usize),
     current_position: (usize, usize),
+    entity_moved: &mut EventChannel<EntityMoved>,
 ) {
     grid[valid_next_position] = Some(transform.entity_id());
     grid[current_position] = None;
@@ -201,6 +229,12 @@ fn move_entity(
     transform
         .inner_mut()
         .set_local_position(grid_to_world_position(valid_next_position));
+
+    entity_moved.send(EntityMoved {
+        entity: transform.entity_id(),
+        from: current_position,
+        to: valid_next_position,
+    });
 }
 
 fn world_to_grid_position(pos: Vec2) -> (usize, usize) {
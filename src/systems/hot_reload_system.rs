@@ -0,0 +1,491 @@
+use super::{
+    prefab_system, serialization_util, Ecs, Entity, FileWatcher, Name, NonInspectableEntities,
+    PostDeserializationRequired, Prefab, RendererComponent, ResourcesDatabase, SceneLayer, SceneMode,
+    SceneStack, SerializationMarker, SerializedEntity, ENTITY_SUBPATH,
+};
+use anyhow::Result;
+use serde_yaml::Value as YamlValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+const SERIALIZED_DATA_DIRECTORY: &str = "assets/serialized_data";
+const TEXTURE_DIRECTORY: &str = "assets/textures";
+
+/// Watches the Scene and Prefab files, so changes made outside the editor, like in a text
+/// editor or by a git checkout, show up in the Scenes we have open. It watches the
+/// textures too, so new art shows up without a rebuild.
+pub struct HotReload {
+    watcher: FileWatcher,
+    texture_watcher: FileWatcher,
+    /// What each open Scene's file had in it when we last looked, so we can tell which
+    /// Entities changed on disk, and which have unsaved edits.
+    scene_files: HashMap<PathBuf, HashMap<Uuid, SerializedEntity>>,
+}
+
+impl HotReload {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            watcher: FileWatcher::new(SERIALIZED_DATA_DIRECTORY, Duration::from_millis(200))?,
+            texture_watcher: FileWatcher::new(TEXTURE_DIRECTORY, Duration::from_millis(200))?,
+            scene_files: HashMap::new(),
+        })
+    }
+
+    /// Remembers what's on disk for any Scene we've just opened, and forgets closed ones.
+    fn track_scenes(&mut self, scene_stack: &mut SceneStack) {
+        let mut open_files = vec![];
+
+        for layer in scene_stack.layers_mut() {
+            let scene = layer.ecs.scene_state.current();
+            let path = PathBuf::from(scene.entity_path());
+
+            if self.scene_files.contains_key(&path) == false {
+                match serialization_util::entities::load_all_entities(scene) {
+                    Ok(entities) => {
+                        self.scene_files.insert(path.clone(), entities);
+                    }
+                    Err(e) => error!("We couldn't load {} to watch it for changes. {}", scene, e),
+                }
+            }
+
+            open_files.push(path);
+        }
+
+        self.scene_files.retain(|path, _| open_files.contains(path));
+    }
+}
+
+/// Reloads any Prefabs which changed on disk, and puts the changes into every open Scene.
+/// Then it does the same for any open Scene whose own file changed. Entities with unsaved
+/// edits keep them, and we warn about it, since saving them will undo the change on disk.
+///
+/// Only Scenes in Draft get changed. A Playing Scene picks the change up when it next loads.
+/// Changes to Components come through, but moving Entities around the Scene Graph, or
+/// adding members to a Prefab, needs the Scene reloaded.
+pub fn reload_changed_files(
+    hot_reload: &mut HotReload,
+    scene_stack: &mut SceneStack,
+    resources: &mut ResourcesDatabase,
+) {
+    hot_reload.track_scenes(scene_stack);
+
+    let changed_files = hot_reload.watcher.changed_files();
+    let mut changed_prefabs = vec![];
+
+    for path in changed_files.iter() {
+        if path.extension().map_or(false, |extension| extension == "prefab") {
+            match serialization_util::prefabs::load_prefab_file(&path.to_string_lossy()) {
+                Ok(prefab) => {
+                    // We've cached the Prefabs the editor saves already
+                    let unchanged = resources
+                        .prefabs()
+                        .get(&prefab.root_id())
+                        .map_or(false, |cached| cached.members == prefab.members);
+
+                    if unchanged == false {
+                        changed_prefabs.push(prefab);
+                    }
+                }
+                Err(e) => error!(
+                    "{} changed on disk, but we couldn't reload it. {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    if changed_prefabs.is_empty() == false {
+        reload_prefabs(changed_prefabs, hot_reload, scene_stack, resources);
+    }
+
+    for path in changed_files.iter() {
+        let is_entity_file = path.file_name().map_or(false, |name| name == ENTITY_SUBPATH)
+            || path.extension().map_or(false, |extension| extension == "prefab");
+        if is_entity_file == false {
+            continue;
+        }
+
+        for layer in scene_stack.layers_mut() {
+            if Path::new(&layer.ecs.scene_state.current().entity_path()) != path.as_path() {
+                continue;
+            }
+
+            if is_drafting(layer) == false {
+                continue;
+            }
+
+            if let Some(on_disk) = hot_reload.scene_files.get_mut(path) {
+                if let Err(e) = reload_scene(&mut layer.ecs, on_disk, resources) {
+                    error!(
+                        "{} changed on disk, but we couldn't reload it. {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Repacks the sprite sheet if any PNG, or the Aseprite JSON which goes with it, changed.
+pub fn reload_changed_textures(
+    hot_reload: &mut HotReload,
+    resources: &mut ResourcesDatabase,
+    renderer: &mut RendererComponent,
+) {
+    let textures_changed = hot_reload.texture_watcher.changed_files().iter().any(|path| {
+        path.extension()
+            .map_or(false, |extension| extension == "png" || extension == "json")
+    });
+
+    if textures_changed {
+        info!("Textures changed on disk. Repacking sprites...");
+        match resources.repack_sprites(renderer) {
+            Ok(()) => info!("..Sprites Repacked!"),
+            Err(e) => error!("We couldn't repack the sprites. {}", e),
+        }
+    }
+}
+
+fn reload_prefabs(
+    changed_prefabs: Vec<Prefab>,
+    hot_reload: &HotReload,
+    scene_stack: &mut SceneStack,
+    resources: &mut ResourcesDatabase,
+) {
+    let changed_ids: Vec<Uuid> = changed_prefabs.iter().map(|prefab| prefab.root_id()).collect();
+
+    // Find the instances before we change the Prefabs, so we can tell if they have unsaved edits
+    let mut instances = vec![];
+    for layer in scene_stack.layers_mut() {
+        let on_disk = hot_reload
+            .scene_files
+            .get(Path::new(&layer.ecs.scene_state.current().entity_path()));
+
+        let layer_instances = match on_disk {
+            Some(on_disk) if is_drafting(layer) => {
+                find_instances(&mut layer.ecs, on_disk, &changed_ids, resources)
+            }
+            _ => vec![],
+        };
+        instances.push(layer_instances);
+    }
+
+    for prefab in changed_prefabs {
+        info!(
+            "Reloaded Prefab {} from disk.",
+            Name::get_name_even_quicklier(
+                prefab
+                    .root_entity()
+                    .name
+                    .as_ref()
+                    .map(|sc| sc.inner.name.as_str()),
+                prefab.root_id(),
+            )
+        );
+        resources.add_prefab(prefab);
+    }
+
+    for (layer, layer_instances) in scene_stack.layers_mut().zip(instances) {
+        reload_entities(&mut layer.ecs, layer_instances, resources);
+    }
+}
+
+/// The serialized instances of the `changed_ids` Prefabs, or of anything inheriting from
+/// them, with what their Scene has on disk for them.
+fn find_instances(
+    ecs: &mut Ecs,
+    on_disk: &HashMap<Uuid, SerializedEntity>,
+    changed_ids: &[Uuid],
+    resources: &ResourcesDatabase,
+) -> Vec<(Entity, SerializedEntity)> {
+    let mut instances = vec![];
+    let mut conflicts = vec![];
+
+    for prefab_marker_c in ecs.component_database.prefab_markers.iter() {
+        let entity = prefab_marker_c.entity_id();
+        let prefab_marker = prefab_marker_c.inner();
+
+        let inherits_changed = prefab_system::prefab_chain(
+            resources.prefabs(),
+            prefab_marker.main_id(),
+            prefab_marker.sub_id(),
+        )
+        .map(|chain| chain.iter().any(|(pm, _)| changed_ids.contains(&pm.main_id())))
+        .unwrap_or_default();
+
+        if inherits_changed == false {
+            continue;
+        }
+
+        // Instances which were never saved have nothing on disk to load
+        let serialized_entity = match ecs
+            .component_database
+            .serialization_markers
+            .get(&entity)
+            .and_then(|sm| on_disk.get(&sm.inner().id))
+        {
+            Some(serialized_entity) => serialized_entity,
+            None => continue,
+        };
+
+        if has_unsaved_edits(ecs, &entity, serialized_entity, resources) {
+            conflicts.push(entity);
+            continue;
+        }
+
+        instances.push((entity, serialized_entity.clone()));
+    }
+
+    for entity in conflicts {
+        warn_unsaved_edits(ecs, entity);
+    }
+
+    instances
+}
+
+/// Loads what's now in the Scene's file into the Entities which changed on disk, and any
+/// Entities which are new to it.
+fn reload_scene(
+    ecs: &mut Ecs,
+    on_disk: &mut HashMap<Uuid, SerializedEntity>,
+    resources: &ResourcesDatabase,
+) -> Result<()> {
+    let new_on_disk = serialization_util::entities::load_all_entities(ecs.scene_state.current())?;
+    let live: Vec<(Entity, Uuid)> = ecs
+        .component_database
+        .serialization_markers
+        .iter()
+        .map(|sm| (sm.entity_id(), sm.inner().id))
+        .collect();
+
+    let mut changed = vec![];
+    for (entity, id) in live.iter() {
+        let new_serialized_entity = match new_on_disk.get(id) {
+            Some(serialized_entity) => serialized_entity,
+            None => {
+                if on_disk.contains_key(id) {
+                    info!(
+                        "{} was taken out of {} on disk, but it stays until the Scene reloads.",
+                        Name::get_name_quick(&ecs.component_database.names, entity),
+                        ecs.scene_state.current()
+                    );
+                }
+                continue;
+            }
+        };
+
+        let old_serialized_entity = on_disk.get(id);
+        if old_serialized_entity == Some(new_serialized_entity) {
+            continue;
+        }
+
+        // The editor just saved it, so there's nothing to load
+        let live_serialized_entity = live_serialized_entity(ecs, entity, *id, resources);
+        if live_serialized_entity.as_ref() == Some(new_serialized_entity) {
+            ecs.reload_conflicts.retain(|conflict| conflict != entity);
+            continue;
+        }
+
+        if live_serialized_entity.as_ref() != old_serialized_entity {
+            warn_unsaved_edits(ecs, *entity);
+            continue;
+        }
+
+        changed.push((*entity, new_serialized_entity.clone()));
+    }
+
+    reload_entities(ecs, changed, resources);
+
+    let mut post_deserialization = None;
+    let mut new_entities = vec![];
+    for (id, serialized_entity) in new_on_disk.iter() {
+        // If it was on disk already, it's been deleted in the editor, and not saved yet
+        if on_disk.contains_key(id) || live.iter().any(|(_, live_id)| live_id == id) {
+            continue;
+        }
+
+        let new_entity = Ecs::create_entity_raw(
+            &mut ecs.component_database,
+            &mut ecs.entity_allocator,
+            &mut ecs.entities,
+        );
+        if let Some(post) = ecs.component_database.load_serialized_entity(
+            &new_entity,
+            serialized_entity.clone(),
+            &mut ecs.entity_allocator,
+            &mut ecs.entities,
+            &mut ecs.singleton_database.associated_entities,
+            resources.prefabs(),
+        ) {
+            post_deserialization = Some(post);
+            new_entities.push(new_entity);
+        }
+    }
+
+    if let Some(post) = post_deserialization {
+        info!(
+            "Loaded {} new Entities into {} from disk.",
+            new_entities.len(),
+            ecs.scene_state.current()
+        );
+        finish_reload(ecs, post, &new_entities);
+    }
+
+    *on_disk = new_on_disk;
+    Ok(())
+}
+
+/// Puts each SerializedEntity into its Entity, Component by Component, so we leave alone
+/// anything which didn't change, like its place in the Scene Graph.
+fn reload_entities(ecs: &mut Ecs, reloads: Vec<(Entity, SerializedEntity)>, resources: &ResourcesDatabase) {
+    let mut post_deserialization = None;
+    let mut reloaded = vec![];
+
+    for (entity, serialized_entity) in reloads {
+        let id = serialized_entity.id;
+        let serialized_yaml = match resolved_yaml(serialized_entity, resources) {
+            Ok(serialized_yaml) => serialized_yaml,
+            Err(e) => {
+                error!(
+                    "We couldn't reload {} from disk. {}",
+                    Name::get_name_quick(&ecs.component_database.names, &entity),
+                    e
+                );
+                continue;
+            }
+        };
+
+        let mut deltas = vec![];
+        let mut reloaded_lists = NonInspectableEntities::all();
+        reloaded_lists.remove(NonInspectableEntities::SERIALIZATION | NonInspectableEntities::GRAPH_NODE);
+
+        ecs.component_database
+            .foreach_component_list_mut(reloaded_lists, |component_list| {
+                let key = component_list.get_yaml_component_key();
+                let on_disk = serialized_yaml[&key].clone();
+
+                if on_disk == component_list.create_yaml_component(&entity) {
+                    return;
+                }
+
+                if on_disk.is_null() {
+                    component_list.unset(&entity);
+                } else {
+                    deltas.push((key, on_disk));
+                }
+            });
+
+        for (key, delta) in deltas {
+            post_deserialization = Some(ecs.component_database.load_yaml_delta_into_database(
+                &entity,
+                key,
+                delta,
+                Default::default(),
+                &mut ecs.singleton_database.associated_entities,
+            ));
+        }
+
+        // A new marker looks at the disk again, so the inspector sees the change
+        ecs.component_database
+            .serialization_markers
+            .set_component(&entity, SerializationMarker::with_id(id));
+
+        info!(
+            "Reloaded {} from disk.",
+            Name::get_name_quick(&ecs.component_database.names, &entity)
+        );
+        ecs.reload_conflicts.retain(|conflict| *conflict != entity);
+        reloaded.push(entity);
+    }
+
+    if let Some(post) = post_deserialization {
+        finish_reload(ecs, post, &reloaded);
+    }
+}
+
+/// Everything a SerializedEntity has, with what it inherits from its Prefab filled in.
+fn resolved_yaml(serialized_entity: SerializedEntity, resources: &ResourcesDatabase) -> Result<YamlValue> {
+    let serialized_entity = match serialized_entity
+        .prefab_marker
+        .as_ref()
+        .map(|pm| pm.inner.clone())
+    {
+        Some(prefab_marker) => prefab_system::load_override_into_prefab(
+            prefab_system::resolve_prefab_member(
+                resources.prefabs(),
+                prefab_marker.main_id(),
+                prefab_marker.sub_id(),
+            )?,
+            serialized_entity,
+        )?,
+        None => serialized_entity,
+    };
+
+    Ok(serde_yaml::to_value(serialized_entity)?)
+}
+
+fn finish_reload(ecs: &mut Ecs, post: PostDeserializationRequired, entities: &[Entity]) {
+    ecs.component_database
+        .post_deserialization(post, &mut ecs.scene_graph, |component_list, sl| {
+            for entity in entities.iter() {
+                if let Some((inner, _)) = component_list.get_mut(entity) {
+                    inner.post_deserialization(*entity, sl);
+                }
+            }
+        });
+
+    ecs.tag_index.rebuild(&mut ecs.component_database.tags);
+}
+
+fn is_drafting(layer: &SceneLayer) -> bool {
+    if layer.ecs.scene_state.current_scene_mode() == SceneMode::Draft {
+        true
+    } else {
+        info!(
+            "{} isn't in Draft, so it'll only pick up changes on disk when it next loads.",
+            layer.ecs.scene_state.current()
+        );
+        false
+    }
+}
+
+fn live_serialized_entity(
+    ecs: &Ecs,
+    entity: &Entity,
+    id: Uuid,
+    resources: &ResourcesDatabase,
+) -> Option<SerializedEntity> {
+    SerializedEntity::new(
+        entity,
+        id,
+        &ecs.component_database,
+        &ecs.singleton_database,
+        resources,
+    )
+}
+
+fn has_unsaved_edits(
+    ecs: &Ecs,
+    entity: &Entity,
+    on_disk: &SerializedEntity,
+    resources: &ResourcesDatabase,
+) -> bool {
+    live_serialized_entity(ecs, entity, on_disk.id, resources).as_ref() != Some(on_disk)
+}
+
+/// Logs the conflict, and lists it in the editor's main menu bar until it's sorted out.
+fn warn_unsaved_edits(ecs: &mut Ecs, entity: Entity) {
+    warn!(
+        "{} changed on disk, but it has unsaved edits in {}, so we kept those. Saving it will undo the change on disk. Revert it to load the change instead.",
+        Name::get_name_quick(&ecs.component_database.names, &entity),
+        ecs.scene_state.current()
+    );
+
+    if ecs.reload_conflicts.contains(&entity) == false {
+        ecs.reload_conflicts.push(entity);
+    }
+}
@@ -15,18 +15,24 @@ pub fn entity_inspector(
     let Ecs {
         component_database,
         singleton_database,
-        entity_allocator: _,
         entities,
+        scene_graph,
+        scene_state,
+        commands,
+        ..
     } = ecs;
 
-    let scene_is_prefab = {
-        let scene_data = scene_system::CURRENT_SCENE.lock().unwrap();
-        scene_data.is_prefab()
-    };
+    let scene = scene_state.current();
+    let scene_is_prefab = scene.is_prefab();
 
     for entity in ui_handler.stored_ids.iter() {
         let mut window_is_open = true;
 
+        // Anything in here can be edited, so the entity list can't trust its last SyncStatus.
+        if let Some(serialization_marker) = component_database.serialization_markers.get_mut(entity) {
+            serialization_marker.inner_mut().forget_serialization_status();
+        }
+
         let window_name = {
             match component_database.names.get_mut(entity) {
                 Some(name) => im_str!("{} (Scene Entity)###{}", &name.inner().name, entity),
@@ -48,6 +54,17 @@ pub fn entity_inspector(
             }
         };
 
+        // Each Prefab this Entity inherits from, nearest first, with only its own overrides.
+        let prefab_levels: Vec<SerializedEntity> = component_database
+            .prefab_markers
+            .get(entity)
+            .and_then(|pm| {
+                prefab_system::prefab_chain(resources.prefabs(), pm.inner().main_id(), pm.inner().sub_id())
+                    .ok()
+            })
+            .map(|chain| chain.into_iter().map(|(_, member)| member.clone()).collect())
+            .unwrap_or_default();
+
         let names = &component_database.names;
         let serialized_entity = component_database
             .serialization_markers
@@ -57,7 +74,7 @@ pub fn entity_inspector(
 
                 let cached_se: SerializedEntity = se
                     .inner_mut()
-                    .cached_serialized_entity()
+                    .cached_serialized_entity(scene)
                     .cloned()
                     .unwrap_or_default();
 
@@ -90,22 +107,45 @@ pub fn entity_inspector(
             component_database.foreach_component_list_mut(
                 NonInspectableEntities::empty(),
                 |component_list| {
-                    let possible_sync_statuses = component_list.get_sync_status(
-                        entity,
-                        serialized_entity.as_ref(),
-                        serialized_prefab.as_ref(),
-                        should_have_serialized_entity,
-                        should_have_prefab,
-                    );
+                    let possible_sync_statuses = component_list
+                        .get_sync_status(
+                            entity,
+                            serialized_entity.as_ref(),
+                            serialized_prefab.as_ref(),
+                            should_have_serialized_entity,
+                            should_have_prefab,
+                        )
+                        .map(|mut sync_status| {
+                            sync_status.prefab_level = prefab_levels.iter().position(|level| {
+                                component_list.get_yaml_component(level).is_null() == false
+                            });
+
+                            if let Some(serialized_prefab) = serialized_prefab.as_ref() {
+                                let prefab_component = component_list.get_yaml_component(serialized_prefab);
+                                if prefab_component.is_null() == false {
+                                    sync_status.overridden_fields = prefab_system::changed_paths(
+                                        component_list
+                                            .get_yaml_component_key()
+                                            .as_str()
+                                            .unwrap_or_default(),
+                                        &prefab_component,
+                                        &component_list.create_yaml_component(entity),
+                                    );
+                                }
+                            }
+                            sync_status
+                        });
 
                     if let Some(command_type) = component_list.component_inspector(
                         entity,
                         possible_sync_statuses,
+                        scene.mode(),
                         entities,
                         unsafe { &*names_raw_pointer },
                         resources.prefabs(),
                         ui,
                         window_is_open,
+                        commands,
                     ) {
                         final_post_action = Some(handle_serialization_command(
                             *entity,
@@ -134,6 +174,9 @@ pub fn entity_inspector(
                     s_marker,
                     SyncStatus::Synced,
                     SyncStatus::Unsynced,
+                    None,
+                    &[],
+                    scene.mode(),
                     entities,
                     &component_database.names,
                     resources.prefabs(),
@@ -141,7 +184,7 @@ pub fn entity_inspector(
                     window_is_open,
                     false,
                     |inner, ip| {
-                        if inner.entity_inspector_results(ip) {
+                        if inner.entity_inspector_results(scene, ip) {
                             final_post_action = Some(ComponentInspectorPostAction::EntityCommands(
                                 EntitySerializationCommand {
                                     entity: *entity,
@@ -178,24 +221,12 @@ pub fn entity_inspector(
                     ),
                     true,
                 ) {
-                    // @update_components exception
-                    let had_transform = component_database.transforms.get(entity).is_some();
-
                     // Prefab Marker, Name, Graph Node is omitted
                     component_database.foreach_component_list_mut(
                         NonInspectableEntities::SERIALIZATION,
-                        |component_list| component_list.component_add_button(entity, ui),
+                        |component_list| component_list.component_add_button(entity, ui, commands),
                     );
 
-                    if had_transform == false {
-                        if let Some(new_transform) = component_database.transforms.get_mut(entity) {
-                            scene_graph::add_to_scene_graph(
-                                new_transform,
-                                &component_database.serialization_markers,
-                            );
-                        }
-                    }
-
                     add_component_submenu.end(ui);
                 }
 
@@ -248,29 +279,42 @@ pub fn entity_inspector(
                             .unwrap();
 
                         let mut serialized_yaml = serde_yaml::to_value(
-                            serialization_util::entities::load_entity_by_id(&uuid)?.unwrap(),
+                            serialization_util::entities::load_entity_by_id(scene, &uuid)?.unwrap(),
                         )?;
 
                         // Insert our New Serialization
+                        let key = command.key.as_str().unwrap_or_default().to_string();
                         serialized_yaml
                             .as_mapping_mut()
                             .unwrap()
                             .insert(command.key, command.delta);
 
-                        let new_serialized_entity: SerializedEntity =
+                        let mut new_serialized_entity: SerializedEntity =
                             serde_yaml::from_value(serialized_yaml)?;
 
+                        // And which of its fields override the Prefab now
+                        let mut serialized_prefab = SerializedEntity::default();
+                        if prefab_system::get_serialized_parent_prefab_from_inheritor(
+                            component_database.prefab_markers.get(&command.entity),
+                            resources,
+                            &mut serialized_prefab,
+                        ) {
+                            prefab_system::refresh_prefab_overrides(
+                                &mut new_serialized_entity,
+                                &serialized_prefab,
+                                &key,
+                            );
+                        }
+
                         serialization_util::entities::commit_entity_to_serialized_scene(
+                            scene,
                             new_serialized_entity.clone(),
                         )?;
 
                         if scene_is_prefab {
                             // THIS IS A CRAZY PERSON WAY TO DO THIS. EVERYTHING CAN GO WRONG
                             // LETS DO IT:
-                            let root_entity_id: Entity =
-                                scene_graph::ROOT_NODES.lock().unwrap().children.as_ref().unwrap()[0]
-                                    .target
-                                    .unwrap();
+                            let root_entity_id: Entity = scene_graph.root_entities().next().unwrap();
                             let root_uuid = component_database
                                 .serialization_markers
                                 .get(&root_entity_id)
@@ -282,7 +326,8 @@ pub fn entity_inspector(
                         }
                     }
                     ComponentSerializationCommandType::Revert
-                    | ComponentSerializationCommandType::RevertToParentPrefab => {
+                    | ComponentSerializationCommandType::RevertToParentPrefab
+                    | ComponentSerializationCommandType::RevertFieldToParentPrefab(_) => {
                         let uuid = component_database
                             .serialization_markers
                             .get(&command.entity)
@@ -304,44 +349,37 @@ pub fn entity_inspector(
                             &mut singleton_database.associated_entities,
                         );
 
-                        component_database.post_deserialization(post_deserialization, |component_list, sl| {
-                            if let Some((inner, _)) = component_list.get_mut(&entity) {
-                                inner.post_deserialization(entity, sl);
-                            }
-                        })
+                        component_database.post_deserialization(
+                            post_deserialization,
+                            scene_graph,
+                            |component_list, sl| {
+                                if let Some((inner, _)) = component_list.get_mut(&entity) {
+                                    inner.post_deserialization(entity, sl);
+                                }
+                            },
+                        )
                     }
 
-                    ComponentSerializationCommandType::ApplyOverrideToParentPrefab => {
+                    ComponentSerializationCommandType::ApplyOverrideToParentPrefab
+                    | ComponentSerializationCommandType::ApplyFieldToParentPrefab(_) => {
                         let (main_id, sub_id) = component_database
                             .prefab_markers
                             .get(&command.entity)
                             .map(|pm| (pm.inner().main_id(), pm.inner().sub_id()))
                             .unwrap();
 
-                        let mut prefab = serialization_util::prefabs::load_prefab(&main_id)?.unwrap();
-                        let (new_member, _diff): (SerializedEntity, _) = {
-                            let mut member_yaml =
-                                serde_yaml::to_value(prefab.members.get(&sub_id).cloned().unwrap())?;
-
-                            let diff = member_yaml
-                                .as_mapping_mut()
-                                .unwrap()
-                                .insert(command.key.clone(), command.delta.clone());
-
-                            (serde_yaml::from_value(member_yaml)?, diff)
+                        let path = match command.command_type {
+                            ComponentSerializationCommandType::ApplyFieldToParentPrefab(path) => path,
+                            _ => command.key.as_str().unwrap_or_default().to_string(),
                         };
 
-                        prefab.members.insert(new_member.id, new_member);
-
-                        let prefab_reload_required =
-                            prefab_system::serialize_and_cache_prefab(prefab, sub_id, resources);
-
-                        prefab_system::post_prefab_serialization(
-                            ecs,
-                            command.key,
+                        // We apply it once the user has seen what else it changes.
+                        *ui_handler.prefab_propagation = Some(prefab_system::PrefabPropagation::dry_run(
+                            main_id,
+                            sub_id,
+                            path,
                             command.delta,
-                            prefab_reload_required,
-                        )?;
+                        )?);
                     }
                 }
 
@@ -356,6 +394,90 @@ pub fn entity_inspector(
     Ok(entity_command)
 }
 
+/// The dry run of an "Apply Overrides To Prefab", listing every other Scene and Prefab
+/// which would lose its override, with buttons to go ahead or not.
+pub fn prefab_propagation_window(
+    ecs: &mut Ecs,
+    resources: &mut ResourcesDatabase,
+    ui_handler: &mut UiHandler<'_>,
+) {
+    let propagation = match ui_handler.prefab_propagation.as_ref() {
+        Some(propagation) => propagation,
+        None => return,
+    };
+
+    let ui = &ui_handler.ui;
+    let mut apply = false;
+    let mut cancel = false;
+
+    let window = Window::new(im_str!("Apply to Prefab"))
+        .size([500.0, 300.0], Condition::FirstUseEver)
+        .collapsible(false);
+
+    if let Some(propagation_window) = window.begin(ui) {
+        let prefab_name = resources
+            .prefabs()
+            .get(&propagation.main_id)
+            .and_then(|prefab| prefab.root_entity().name.as_ref())
+            .map_or_else(|| propagation.main_id.to_string(), |name| name.inner.name.clone());
+
+        ui.text(im_str!(
+            "Applying {} to Prefab {}.",
+            propagation.path,
+            prefab_name
+        ));
+
+        let report = &propagation.report;
+        if report.overriding_files.is_empty() {
+            ui.text("Nothing else on disk overrides it.");
+        } else {
+            ui.text("These inheritors override it, and will lose their override:");
+            for overriding_file in report.overriding_files.iter() {
+                ui.bullet_text(&im_str!("{}", overriding_file.path));
+                ui.indent();
+                for (id, name) in overriding_file.entities.iter() {
+                    ui.text(im_str!(
+                        "{}",
+                        Name::get_name_even_quicklier(name.as_ref().map(String::as_str), id)
+                    ));
+                }
+                ui.unindent();
+            }
+        }
+
+        if report.unchecked_files.is_empty() == false {
+            imgui_system::wrap_style_color_var(
+                ui,
+                StyleColor::Text,
+                imgui_utility::red_warning_color(),
+                || {
+                    ui.text("We couldn't check these files, so they won't change:");
+                    for unchecked_file in report.unchecked_files.iter() {
+                        ui.bullet_text(&im_str!("{}", unchecked_file));
+                    }
+                },
+            );
+        }
+
+        ui.separator();
+        apply = ui.button(im_str!("Apply"), [0.0, 0.0]);
+        ui.same_line(0.0);
+        cancel = ui.button(im_str!("Cancel"), [0.0, 0.0]);
+
+        propagation_window.end(ui);
+    }
+
+    if apply {
+        if let Some(propagation) = ui_handler.prefab_propagation.take() {
+            if let Err(e) = propagation.apply(ecs, resources) {
+                error!("We couldn't apply to the Prefab! {}", e);
+            }
+        }
+    } else if cancel {
+        *ui_handler.prefab_propagation = None;
+    }
+}
+
 pub fn serialization_menu(
     serialized_marker: &SerializationMarker,
     ui: &Ui<'_>,
@@ -416,6 +538,9 @@ pub fn component_inspector_raw<T>(
     comp: &mut Component<T>,
     serialization_sync_status: SyncStatus,
     prefab_sync_status: SyncStatus,
+    prefab_level: Option<usize>,
+    overridden_fields: &[String],
+    scene_mode: SceneMode,
     entities: &[Entity],
     entity_names: &ComponentList<Name>,
     prefabs: &PrefabMap,
@@ -430,7 +555,6 @@ where
     let mut requested_action = None;
     let mut delete = false;
 
-    let scene_mode = scene_system::current_scene_mode();
     let name = super::imgui_system::typed_text_ui::<T>();
     let uid = &format!("{}{}", comp.entity_id(), &T::type_name());
 
@@ -443,7 +567,7 @@ where
 
     let text_color_token = ui.push_style_color(
         imgui::StyleColor::Text,
-        prefab_sync_status.imgui_color(scene_mode),
+        prefab_sync_status.prefab_imgui_color(scene_mode, prefab_level),
     );
 
     ui.tree_node(&imgui::ImString::new(&name))
@@ -453,11 +577,27 @@ where
             imgui_utility::wrap_style_var(ui, StyleVar::Alpha(1.0), || {
                 // This is the Hover here:
                 if ui.is_item_hovered() {
-                    ui.tooltip_text(match prefab_sync_status {
-                        SyncStatus::Unsynced => "This Entity does not inherit from a Prefab.",
-                        SyncStatus::Headless => "This Componet is HEADLESS to its PREFAB!",
-                        SyncStatus::OutofSync => "Overriding Prefab Parent",
-                        SyncStatus::Synced => "Synced to Prefab Parent",
+                    ui.tooltip_text(match (prefab_sync_status, prefab_level) {
+                        (SyncStatus::Unsynced, _) => {
+                            "This Entity does not inherit from a Prefab.".to_string()
+                        }
+                        (SyncStatus::Headless, _) => "This Componet is HEADLESS to its PREFAB!".to_string(),
+                        (SyncStatus::OutofSync, _) if overridden_fields.is_empty() == false => format!(
+                            "Overriding Prefab Parent in {}",
+                            overridden_fields
+                                .iter()
+                                .map(|path| field_label(path))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        (SyncStatus::OutofSync, _) => "Overriding Prefab Parent".to_string(),
+                        (SyncStatus::Synced, Some(level)) if level > 0 => {
+                            format!(
+                                "Synced to Prefab Parent, which inherits it from {} Prefab(s) up",
+                                level
+                            )
+                        }
+                        (SyncStatus::Synced, _) => "Synced to Prefab Parent".to_string(),
                     });
                 }
 
@@ -470,6 +610,7 @@ where
                         default_color,
                         serialization_sync_status,
                         prefab_sync_status,
+                        overridden_fields,
                     );
                     requested_action = right_click_actions.0;
                     delete = right_click_actions.1;
@@ -571,6 +712,7 @@ fn component_inspector_right_click(
     default_color: ImColor,
     serialization_sync_status: SyncStatus,
     prefab_sync_status: SyncStatus,
+    overridden_fields: &[String],
 ) -> (Option<ComponentSerializationCommandType>, bool) {
     let mut requested_action = None;
     let mut delete = false;
@@ -621,12 +763,51 @@ fn component_inspector_right_click(
             {
                 requested_action = Some(ComponentSerializationCommandType::RevertToParentPrefab);
             }
+
+            if let Some(apply_menu) = ui.begin_menu(
+                &imgui_str("Apply Field To Prefab", uid),
+                overridden_fields.is_empty() == false,
+            ) {
+                for path in overridden_fields {
+                    if MenuItem::new(&im_str!("{}##apply{}", field_label(path), uid)).build(ui) {
+                        requested_action = Some(ComponentSerializationCommandType::ApplyFieldToParentPrefab(
+                            path.clone(),
+                        ));
+                    }
+                }
+                apply_menu.end(ui);
+            }
+
+            if let Some(revert_menu) = ui.begin_menu(
+                &imgui_str("Revert Field to Prefab", uid),
+                overridden_fields.is_empty() == false,
+            ) {
+                for path in overridden_fields {
+                    if MenuItem::new(&im_str!("{}##revert{}", field_label(path), uid)).build(ui) {
+                        requested_action = Some(
+                            ComponentSerializationCommandType::RevertFieldToParentPrefab(path.clone()),
+                        );
+                    }
+                }
+                revert_menu.end(ui);
+            }
         });
     });
 
     (requested_action, delete)
 }
 
+/// A field's path without its Component, or the `inner` every Component has,
+/// so `sprite.inner.sprite_name` is just `sprite_name`.
+fn field_label(path: &str) -> &str {
+    let field = path.splitn(2, '.').nth(1).unwrap_or(path);
+    if field.starts_with("inner.") {
+        &field["inner.".len()..]
+    } else {
+        field
+    }
+}
+
 impl<T> ComponentList<T>
 where
     T: ComponentBounds + Clone + typename::TypeName + std::fmt::Debug + 'static,
@@ -752,5 +933,48 @@ fn handle_serialization_command(
                 entity,
             })
         }
+        ComponentSerializationCommandType::ApplyFieldToParentPrefab(ref path) => {
+            let mut live_entity = serde_yaml::Value::Mapping(Default::default());
+            prefab_system::set_path(
+                &mut live_entity,
+                prefab_system::path_component(path),
+                component_list.create_yaml_component(&entity),
+            );
+
+            ComponentInspectorPostAction::ComponentCommands(ComponentSerializationCommand {
+                delta: prefab_system::get_path(&live_entity, path)
+                    .cloned()
+                    .unwrap_or_default(),
+                key: component_list.get_yaml_component_key(),
+                command_type,
+                entity,
+            })
+        }
+        ComponentSerializationCommandType::RevertFieldToParentPrefab(ref path) => {
+            // We put the Prefab's field into the live Component, and revert to that
+            let key = prefab_system::path_component(path);
+            let mut live_entity = serde_yaml::Value::Mapping(Default::default());
+            prefab_system::set_path(
+                &mut live_entity,
+                key,
+                component_list.create_yaml_component(&entity),
+            );
+
+            if let Some(prefab_field) = serialized_prefab
+                .and_then(|sp| serde_yaml::to_value(sp).ok())
+                .and_then(|sp| prefab_system::get_path(&sp, path).cloned())
+            {
+                prefab_system::set_path(&mut live_entity, path, prefab_field);
+            }
+
+            ComponentInspectorPostAction::ComponentCommands(ComponentSerializationCommand {
+                delta: prefab_system::get_path(&live_entity, key)
+                    .cloned()
+                    .unwrap_or_default(),
+                key: component_list.get_yaml_component_key(),
+                command_type,
+                entity,
+            })
+        }
     }
 }
@@ -20,6 +20,12 @@ pub fn entity_list(
             NameRequestedAction::ChangeName(new_name) => {
                 let name_component = ecs.component_database.names.get_mut_or_default(&entity);
                 name_component.inner_mut().name = new_name;
+
+                if let Some(serialization_marker) =
+                    ecs.component_database.serialization_markers.get_mut(&entity)
+                {
+                    serialization_marker.inner_mut().forget_serialization_status();
+                }
             }
             NameRequestedAction::ToggleInspect => {
                 if ui_handler.stored_ids.contains(&entity) {
@@ -37,13 +43,13 @@ pub fn entity_list(
                 }
             }
             NameRequestedAction::Delete => {
-                ecs.remove_entity(&entity);
+                ecs.commands.despawn(entity);
                 ui_handler.stored_ids.remove(&entity);
             }
             NameRequestedAction::GoToPrefab => {
                 if let Some(prefab_marker) = ecs.component_database.prefab_markers.get(&entity) {
                     let id = prefab_marker.inner().main_id();
-                    if scene_system::set_next_scene(Scene::new_prefab(id)) == false {
+                    if ecs.scene_state.set_next_scene(Scene::new_prefab(id)) == false {
                         error!("Couldn't switch to Prefab {}", id);
                         error!("Does a Prefab by that name exist?");
                     }
@@ -56,6 +62,17 @@ pub fn entity_list(
             }
             NameRequestedAction::PromoteToPrefab => {
                 prefab_system::commit_new_prefab(
+                    ecs.scene_state.current(),
+                    &entity,
+                    &mut ecs.component_database,
+                    &ecs.singleton_database,
+                    resources,
+                )?;
+            }
+
+            NameRequestedAction::PromoteToVariant => {
+                prefab_system::commit_instance_as_variant(
+                    ecs.scene_state.current(),
                     &entity,
                     &mut ecs.component_database,
                     &ecs.singleton_database,
@@ -63,6 +80,21 @@ pub fn entity_list(
                 )?;
             }
 
+            NameRequestedAction::CreateVariant => {
+                if let Some(prefab_marker) = ecs.component_database.prefab_markers.get(&entity) {
+                    prefab_system::commit_prefab_variant(prefab_marker.inner().main_id(), resources)?;
+                }
+            }
+
+            NameRequestedAction::FindUsages => {
+                if let Some(prefab_marker) = ecs.component_database.prefab_markers.get(&entity) {
+                    *ui_handler.prefab_usages = Some(prefab_system::PrefabUsages::find(
+                        prefab_marker.inner().main_id(),
+                        false,
+                    )?);
+                }
+            }
+
             NameRequestedAction::UnpackPrefab => {
                 let mut success = false;
 
@@ -71,6 +103,7 @@ pub fn entity_list(
                         ecs.component_database.serialization_markers.get(&entity)
                     {
                         let serialized_entity = serialization_util::entities::load_committed_entity(
+                            ecs.scene_state.current(),
                             &serialization_marker.inner(),
                         );
 
@@ -78,6 +111,7 @@ pub fn entity_list(
                             prefab_marker.inner().uncommit_to_scene(&mut serialized_entity);
 
                             success = serialization_util::entities::commit_entity_to_serialized_scene(
+                                ecs.scene_state.current(),
                                 serialized_entity,
                             )
                             .is_ok();
@@ -116,7 +150,10 @@ pub fn entity_list(
                 if let Some(serialization_marker) =
                     ecs.component_database.serialization_markers.get_mut(&entity)
                 {
-                    if let Some(cached) = serialization_marker.inner_mut().cached_serialized_entity() {
+                    if let Some(cached) = serialization_marker
+                        .inner_mut()
+                        .cached_serialized_entity(ecs.scene_state.current())
+                    {
                         cached.log_to_console();
                     } else {
                         error!(
@@ -190,16 +227,7 @@ fn imgui_entity_list(
                     };
 
                     if imgui::MenuItem::new(&name).build(ui) {
-                        let entity = prefab_system::instantiate_entity_from_prefab(
-                            ecs,
-                            *prefab_id,
-                            resources.prefabs(),
-                        );
-                        if scene_system::current_scene_mode() == SceneMode::Draft {
-                            ecs.component_database
-                                .serialization_markers
-                                .set_component(&entity, SerializationMarker::new());
-                        }
+                        ecs.commands.spawn_prefab(*prefab_id);
                     }
                 }
 
@@ -212,8 +240,36 @@ fn imgui_entity_list(
                 prefab_submenu.end(ui);
             }
 
-            if imgui::MenuItem::new(im_str!("Serialize Scene")).build(ui) || ui_handler.can_save_scene() {
+            // FIND
+            if let Some(find_submenu) = ui.begin_menu(im_str!("Find"), true) {
+                let mut im_query = imgui::ImString::new(ui_handler.find_query.as_str());
+                if ui
+                    .input_text(im_str!("Tag or Path"), &mut im_query)
+                    .resize_buffer(true)
+                    .build()
+                {
+                    *ui_handler.find_query = im_query.to_string();
+                }
+
+                if ui.button(im_str!("Inspect"), [0.0, 0.0]) {
+                    let query = ui_handler.find_query.as_str();
+                    let mut found = ecs.find_tagged(query).to_vec();
+                    found.extend(ecs.find_by_path(query));
+
+                    if found.is_empty() {
+                        info!("Nothing is tagged or at the path {}", query);
+                    }
+                    ui_handler.stored_ids.extend(found);
+                }
+
+                find_submenu.end(ui);
+            }
+
+            if imgui::MenuItem::new(im_str!("Serialize Scene")).build(ui)
+                || ui_handler.can_save_scene(ecs.scene_state.current_scene_mode())
+            {
                 match serialization_util::entities::serialize_all_entities(
+                    ecs.scene_state.current(),
                     &ecs.entities,
                     &ecs.component_database,
                     &ecs.singleton_database,
@@ -232,25 +288,14 @@ fn imgui_entity_list(
         ui_handler.scene_graph_entities.clear();
 
         // SCENE GRAPH
-        scene_graph::walk_graph_inspect(
+        let scene = ecs.scene_state.current();
+        ecs.scene_graph.walk_graph_inspect(
+            scene,
             &mut ecs.component_database,
             &mut ecs.singleton_database,
             resources,
-            &mut |entity,
-                  names,
-                  serialization_data,
-                  current_serialized_entity,
-                  prefabs,
-                  mut name_inspector_params| {
+            &mut |entity, names, prefabs, mut name_inspector_params| {
                 // Update Name Inspector Parameter:
-                name_inspector_params.serialization_status = serialization_data
-                    .get_mut(entity)
-                    .map(|smc| {
-                        smc.inner_mut()
-                            .get_serialization_status(current_serialized_entity.as_ref())
-                    })
-                    .unwrap_or_default();
-
                 name_inspector_params.being_inspected = ui_handler.stored_ids.contains(entity);
                 name_inspector_params.prefab_status = prefabs
                     .get(entity)
@@ -278,35 +323,13 @@ fn imgui_entity_list(
 
         for entity in entities.iter() {
             if ui_handler.scene_graph_entities.contains(entity) == false {
-                let serialization_status: SyncStatus = {
-                    let serialization_id = component_database
-                        .serialization_markers
-                        .get(entity)
-                        .map(|sc| sc.inner().id);
-
-                    if let Some(s_id) = serialization_id {
-                        let se = SerializedEntity::new(
-                            entity,
-                            s_id,
-                            component_database,
-                            singleton_database,
-                            resources,
-                        );
-
-                        Some(
-                            component_database
-                                .serialization_markers
-                                .get_mut(entity)
-                                .as_mut()
-                                .unwrap()
-                                .inner_mut()
-                                .get_serialization_status(se.as_ref()),
-                        )
-                    } else {
-                        None
-                    }
-                    .unwrap_or_default()
-                };
+                let serialization_status = SerializedEntity::serialization_status(
+                    entity,
+                    scene,
+                    component_database,
+                    singleton_database,
+                    resources,
+                );
 
                 let nip = NameInspectorParameters {
                     prefab_status: component_database
@@ -2,18 +2,39 @@ use super::{imgui_component_utils::EntitySerializationCommand, *};
 
 pub fn imgui_main(
     ecs: &mut Ecs,
+    is_overlay: bool,
     resources: &mut ResourcesDatabase,
     hardware_interfaces: &mut HardwareInterface,
     ui_handler: &mut UiHandler<'_>,
     time_keeper: &TimeKeeper,
+    scheduler: &scheduler::Scheduler,
 ) {
     let mut entity_serialization_command: Option<EntitySerializationCommand> = None;
 
+    // Play Controls work even when the Main Menu Bar is hidden
+    let scene_mode = ecs.scene_state.current_scene_mode();
+    for control in PlayControl::ALL.iter() {
+        if control.enabled(scene_mode) && hardware_interfaces.input.kb_input.is_pressed(control.key()) {
+            control.apply(&mut ecs.scene_state);
+        }
+    }
+
+    // Selected Entities might have been despawned since last frame, and so might conflicts
+    validate_selection(ui_handler.stored_ids, &ecs.entity_allocator);
+    let entity_allocator = &ecs.entity_allocator;
+    ecs.reload_conflicts
+        .retain(|entity| entity_allocator.is_live(*entity));
+
     main_menu_bar(
         hardware_interfaces
             .input
             .kb_input
             .is_pressed(winit::event::VirtualKeyCode::F1),
+        &mut ecs.scene_state,
+        &ecs.load_errors,
+        &mut ecs.reload_conflicts,
+        &ecs.component_database.names,
+        is_overlay,
         ui_handler,
     );
 
@@ -43,9 +64,13 @@ pub fn imgui_main(
         }
     }
 
+    // Confirming an Apply to Prefab
+    imgui_component::prefab_propagation_window(ecs, resources, ui_handler);
+
     // Singleton
     imgui_utility::create_window(ui_handler, ImGuiFlags::SINGLETONS, |ui_handler| {
         imgui_singleton::singleton_inspector(
+            ecs.scene_state.current(),
             &mut ecs.singleton_database,
             &ecs.component_database.names,
             &ecs.entities,
@@ -59,8 +84,13 @@ pub fn imgui_main(
         time_keeper.create_imgui_window(ui_handler)
     });
 
+    // Scheduler
+    imgui_utility::create_window(ui_handler, ImGuiFlags::SCHEDULER, |ui_handler| {
+        scheduler.create_imgui_window(ui_handler)
+    });
+
     // Resources Windows
-    imgui_resources::create_resources_windows(resources, ui_handler);
+    imgui_resources::create_resources_windows(resources, &mut ecs.scene_state, ui_handler);
 
     // Demo window!
     if ui_handler.flags.contains(ImGuiFlags::IMGUI_EXAMPLE) {
@@ -73,11 +103,13 @@ pub fn imgui_main(
 
     if let Some(sc) = entity_serialization_command {
         if let Err(e) = serialization_util::entities::process_serialized_command(
+            ecs.scene_state.current(),
             sc,
             &mut ecs.component_database,
             &mut ecs.singleton_database,
             &mut ecs.entities,
             &mut ecs.entity_allocator,
+            &mut ecs.scene_graph,
             resources,
         ) {
             error!("Error Processing Serialized Command: {}", e);
@@ -95,7 +127,15 @@ pub fn imgui_main(
     }
 }
 
-fn main_menu_bar(toggle_main_menu_bar: bool, ui_handler: &mut UiHandler<'_>) {
+fn main_menu_bar(
+    toggle_main_menu_bar: bool,
+    scene_state: &mut scene_system::SceneState,
+    load_errors: &[LoadError],
+    reload_conflicts: &mut Vec<Entity>,
+    names: &ComponentList<Name>,
+    is_overlay: bool,
+    ui_handler: &mut UiHandler<'_>,
+) {
     if toggle_main_menu_bar {
         ui_handler.flags.toggle(ImGuiFlags::MAIN_MENU_BAR);
     }
@@ -106,15 +146,13 @@ fn main_menu_bar(toggle_main_menu_bar: bool, ui_handler: &mut UiHandler<'_>) {
         if let Some(menu_bar) = ui.begin_main_menu_bar() {
             // SCENE
 
-            if let Some(menu) =
-                ui.begin_menu(&im_str!("{}", &scene_system::CURRENT_SCENE.lock().unwrap()), true)
-            {
+            if let Some(menu) = ui.begin_menu(&im_str!("{}", scene_state.current()), true) {
                 scene_change(
                     "Switch Scene",
                     ui,
                     &mut ui_handler.scene_changing_info.switch_scene_name,
                     |new_name| {
-                        if scene_system::set_next_scene(Scene::new(new_name.to_string())) == false {
+                        if scene_state.set_next_scene(Scene::new(new_name.to_string())) == false {
                             error!("Couldn't switch to Scene {}", new_name);
                             error!("Does a Scene by that name exist?");
                         }
@@ -157,6 +195,56 @@ fn main_menu_bar(toggle_main_menu_bar: bool, ui_handler: &mut UiHandler<'_>) {
                     },
                 );
 
+                ui.separator();
+
+                // OVERLAYS
+                scene_change(
+                    "Push Overlay",
+                    ui,
+                    &mut ui_handler.scene_changing_info.push_overlay_name,
+                    |new_name| {
+                        if scene_state.push_overlay(Scene::new(new_name.to_string()), false) == false {
+                            error!("Couldn't push Scene {} as an Overlay", new_name);
+                            error!("Does a Scene by that name exist?");
+                        }
+                    },
+                );
+
+                scene_change(
+                    "Push Overlay (Keep Updating)",
+                    ui,
+                    &mut ui_handler.scene_changing_info.push_overlay_name,
+                    |new_name| {
+                        if scene_state.push_overlay(Scene::new(new_name.to_string()), true) == false {
+                            error!("Couldn't push Scene {} as an Overlay", new_name);
+                            error!("Does a Scene by that name exist?");
+                        }
+                    },
+                );
+
+                if imgui::MenuItem::new(im_str!("Pop Overlay"))
+                    .enabled(is_overlay)
+                    .build(ui)
+                {
+                    scene_state.pop_overlay();
+                }
+
+                menu.end(ui);
+            }
+
+            // PLAY MODE
+            let scene_mode = scene_state.current_scene_mode();
+            if let Some(menu) = ui.begin_menu(&im_str!("{:?}###Play Mode", scene_mode), true) {
+                for control in PlayControl::ALL.iter() {
+                    if imgui::MenuItem::new(control.label())
+                        .shortcut(control.shortcut())
+                        .enabled(control.enabled(scene_mode))
+                        .build(ui)
+                    {
+                        control.apply(scene_state);
+                    }
+                }
+
                 menu.end(ui);
             }
 
@@ -214,6 +302,13 @@ fn main_menu_bar(toggle_main_menu_bar: bool, ui_handler: &mut UiHandler<'_>) {
                     &mut ui_handler.flags,
                 );
 
+                menu_option(
+                    im_str!("Scheduler"),
+                    ImGuiFlags::SCHEDULER,
+                    ui,
+                    &mut ui_handler.flags,
+                );
+
                 menu_option(
                     im_str!("Game Config Inspector"),
                     ImGuiFlags::GAME_CONFIG,
@@ -227,14 +322,140 @@ fn main_menu_bar(toggle_main_menu_bar: bool, ui_handler: &mut UiHandler<'_>) {
                     ui,
                     &mut ui_handler.flags,
                 );
+
+                ui.separator();
+                if imgui::MenuItem::new(im_str!("Find Stale Entity Handles")).build(ui) {
+                    scene_state.request_handle_validation(false);
+                }
+                if imgui::MenuItem::new(im_str!("Clear Stale Entity Handles")).build(ui) {
+                    scene_state.request_handle_validation(true);
+                }
                 utility_bar.end(ui);
             }
 
+            // LOAD ERRORS
+            if load_errors.is_empty() == false {
+                let text_color_token = ui.push_style_color(imgui::StyleColor::Text, red_warning_color());
+                let menu = ui.begin_menu(
+                    &im_str!("{} {} Quarantined###Load Errors", WARNING_ICON, load_errors.len()),
+                    true,
+                );
+                text_color_token.pop(ui);
+
+                if let Some(menu) = menu {
+                    ui.text("These Entities couldn't load, so they're not in the Scene.");
+                    ui.text("They're still in the file. Fix them there, and reload the Scene.");
+                    ui.separator();
+
+                    for load_error in load_errors {
+                        ui.text(&im_str!("{}", load_error));
+                    }
+                    menu.end(ui);
+                }
+            }
+
+            // RELOAD CONFLICTS
+            if reload_conflicts.is_empty() == false {
+                let text_color_token = ui.push_style_color(imgui::StyleColor::Text, red_warning_color());
+                let menu = ui.begin_menu(
+                    &im_str!(
+                        "{} {} Reload Conflicts###Reload Conflicts",
+                        WARNING_ICON,
+                        reload_conflicts.len()
+                    ),
+                    true,
+                );
+                text_color_token.pop(ui);
+
+                if let Some(menu) = menu {
+                    ui.text("These Entities changed on disk, but had unsaved edits, so we kept the edits.");
+                    ui.text("Saving one undoes the change on disk. Revert it to load the change instead.");
+                    ui.separator();
+
+                    for entity in reload_conflicts.iter() {
+                        let name = Name::get_name_quick(names, entity);
+                        if imgui::MenuItem::new(&im_str!("{}##{}", name, entity)).build(ui) {
+                            ui_handler.stored_ids.insert(*entity);
+                        }
+                    }
+
+                    ui.separator();
+                    if imgui::MenuItem::new(im_str!("Dismiss All")).build(ui) {
+                        reload_conflicts.clear();
+                    }
+                    menu.end(ui);
+                }
+            }
+
             menu_bar.end(ui);
         }
     }
 }
 
+/// The controls which move a Scene between SceneModes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlayControl {
+    Play,
+    Pause,
+    Step,
+    Stop,
+}
+
+impl PlayControl {
+    const ALL: [PlayControl; 4] = [
+        PlayControl::Play,
+        PlayControl::Pause,
+        PlayControl::Step,
+        PlayControl::Stop,
+    ];
+
+    fn label(&self) -> &'static imgui::ImStr {
+        match self {
+            PlayControl::Play => im_str!("Play"),
+            PlayControl::Pause => im_str!("Pause"),
+            PlayControl::Step => im_str!("Step"),
+            PlayControl::Stop => im_str!("Stop"),
+        }
+    }
+
+    fn shortcut(&self) -> &'static imgui::ImStr {
+        match self {
+            PlayControl::Play => im_str!("F5"),
+            PlayControl::Pause => im_str!("F6"),
+            PlayControl::Step => im_str!("F7"),
+            PlayControl::Stop => im_str!("F8"),
+        }
+    }
+
+    fn key(&self) -> winit::event::VirtualKeyCode {
+        match self {
+            PlayControl::Play => winit::event::VirtualKeyCode::F5,
+            PlayControl::Pause => winit::event::VirtualKeyCode::F6,
+            PlayControl::Step => winit::event::VirtualKeyCode::F7,
+            PlayControl::Stop => winit::event::VirtualKeyCode::F8,
+        }
+    }
+
+    /// Play also resumes from Paused. Step works from any SceneMode, and leaves us Paused.
+    fn enabled(&self, scene_mode: SceneMode) -> bool {
+        match self {
+            PlayControl::Play => scene_mode != SceneMode::Playing,
+            PlayControl::Pause => scene_mode == SceneMode::Playing,
+            PlayControl::Step => true,
+            PlayControl::Stop => scene_mode != SceneMode::Draft,
+        }
+    }
+
+    fn apply(&self, scene_state: &mut scene_system::SceneState) {
+        match self {
+            PlayControl::Play => scene_state.request_scene_mode(SceneMode::Playing),
+            PlayControl::Pause => scene_state.request_scene_mode(SceneMode::Paused),
+            PlayControl::Step => scene_state.request_step(),
+            PlayControl::Stop => scene_state.request_scene_mode(SceneMode::Draft),
+        }
+    }
+}
+
 fn menu_option(imstr: &imgui::ImStr, flag: ImGuiFlags, ui: &Ui<'_>, flags_to_change: &mut ImGuiFlags) {
     if imgui::MenuItem::new(imstr)
         .selected(flags_to_change.contains(flag))
@@ -244,7 +465,7 @@ fn menu_option(imstr: &imgui::ImStr, flag: ImGuiFlags, ui: &Ui<'_>, flags_to_cha
     }
 }
 
-fn scene_change<F: Fn(&str)>(prompt: &str, ui: &imgui::Ui<'_>, scene_name: &mut String, on_click: F) {
+fn scene_change<F: FnMut(&str)>(prompt: &str, ui: &imgui::Ui<'_>, scene_name: &mut String, mut on_click: F) {
     let im_prompt = imgui::ImString::new(prompt);
 
     if let Some(scene_submenu) = ui.begin_menu(&im_prompt, true) {
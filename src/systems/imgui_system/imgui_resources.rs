@@ -2,7 +2,11 @@ use super::{imgui_component_utils::*, *};
 use sprite_resources::*;
 use uuid::Uuid;
 
-pub fn create_resources_windows(resources: &mut ResourcesDatabase, ui_handler: &mut UiHandler<'_>) {
+pub fn create_resources_windows(
+    resources: &mut ResourcesDatabase,
+    scene_state: &mut scene_system::SceneState,
+    ui_handler: &mut UiHandler<'_>,
+) {
     imgui_utility::create_window(
         ui_handler,
         ImGuiFlags::SPRITE_RESOURCE,
@@ -24,8 +28,10 @@ pub fn create_resources_windows(resources: &mut ResourcesDatabase, ui_handler: &
     imgui_utility::create_window(
         ui_handler,
         ImGuiFlags::PREFAB_INSPECTOR,
-        |ui_handler: &mut UiHandler<'_>| prefab_entity_viewer(resources, ui_handler),
+        |ui_handler: &mut UiHandler<'_>| prefab_entity_viewer(resources, scene_state, ui_handler),
     );
+
+    prefab_usages_window(resources, scene_state, ui_handler);
 }
 
 pub fn sprite_viewer(resources: &mut ResourcesDatabase, ui_handler: &mut UiHandler<'_>) -> bool {
@@ -275,7 +281,11 @@ pub fn game_config_editor(config: &mut game_config::Config, ui_handler: &mut UiH
     close
 }
 
-pub fn prefab_entity_viewer(resources: &mut ResourcesDatabase, ui_handler: &mut UiHandler<'_>) -> bool {
+pub fn prefab_entity_viewer(
+    resources: &mut ResourcesDatabase,
+    scene_state: &mut scene_system::SceneState,
+    ui_handler: &mut UiHandler<'_>,
+) -> bool {
     let mut open = true;
 
     let mut action_on_prefab: Option<(Uuid, NameRequestedAction)> = None;
@@ -320,31 +330,41 @@ pub fn prefab_entity_viewer(resources: &mut ResourcesDatabase, ui_handler: &mut
                     let clone: Prefab = resources.prefabs().get(&id).unwrap().clone();
                     resources.add_prefab(clone);
                 }
-                NameRequestedAction::Delete => {
-                    // Invalidate our live prefab:
-                    let mut prefab = resources.remove_prefab(&id).unwrap();
-                    prefab.invalidate();
-
-                    if let Err(e) = serialization_util::prefabs::invalidate_prefab(&prefab) {
-                        error!(
-                            "Couldn't invalidate prefab {:?} because {}",
-                            prefab.root_entity().name,
-                            e
-                        );
+                NameRequestedAction::Delete => match prefab_system::PrefabUsages::find(id, true) {
+                    // Nothing uses it, so there's nothing to ask about
+                    Ok(prefab_usages) if prefab_usages.is_unused() => {
+                        if let Err(e) = prefab_usages.delete(resources) {
+                            error!("Couldn't delete Prefab {}: {}", id, e);
+                        }
                     }
-                }
+                    Ok(prefab_usages) => *ui_handler.prefab_usages = Some(prefab_usages),
+                    Err(e) => error!(
+                        "Couldn't find what uses Prefab {}, so we didn't delete it: {}",
+                        id, e
+                    ),
+                },
+                NameRequestedAction::FindUsages => match prefab_system::PrefabUsages::find(id, false) {
+                    Ok(prefab_usages) => *ui_handler.prefab_usages = Some(prefab_usages),
+                    Err(e) => error!("Couldn't find what uses Prefab {}: {}", id, e),
+                },
                 NameRequestedAction::LogEntity | NameRequestedAction::LogPrefab => {
                     let prefab = resources.prefabs().get(&id).unwrap();
                     prefab.log_to_console();
                 }
                 NameRequestedAction::ToggleInspect | NameRequestedAction::GoToPrefab => {
-                    if scene_system::set_next_scene(Scene::new_prefab(id)) == false {
+                    if scene_state.set_next_scene(Scene::new_prefab(id)) == false {
                         error!("Couldn't switch to Prefab {}", id);
                         error!("Does a Prefab by that name exist?");
                     }
                 }
 
-                NameRequestedAction::PromoteToPrefab => {
+                NameRequestedAction::CreateVariant => {
+                    if let Err(e) = prefab_system::commit_prefab_variant(id, resources) {
+                        error!("Couldn't create a variant of Prefab {}: {}", id, e);
+                    }
+                }
+
+                NameRequestedAction::PromoteToPrefab | NameRequestedAction::PromoteToVariant => {
                     unimplemented!();
                 }
             }
@@ -356,6 +376,132 @@ pub fn prefab_entity_viewer(resources: &mut ResourcesDatabase, ui_handler: &mut
     open
 }
 
+/// The "Find Usages" view of a Prefab. When we're deleting the Prefab, this is where the
+/// editor picks what replaces it.
+pub fn prefab_usages_window(
+    resources: &mut ResourcesDatabase,
+    scene_state: &mut scene_system::SceneState,
+    ui_handler: &mut UiHandler<'_>,
+) {
+    let prefab_usages = match ui_handler.prefab_usages.as_mut() {
+        Some(prefab_usages) => prefab_usages,
+        None => return,
+    };
+
+    let ui = &ui_handler.ui;
+    let mut open = true;
+    let mut delete = false;
+    let mut cancel = false;
+
+    let prefab_name = |id: &Uuid| {
+        Name::get_name_even_quicklier(
+            resources
+                .prefabs()
+                .get(id)
+                .and_then(|prefab| prefab.root_entity().name.as_ref())
+                .map(|sc| sc.inner.name.as_str()),
+            *id,
+        )
+    };
+
+    let window = imgui::Window::new(im_str!("Prefab Usages"))
+        .size([500.0, 300.0], imgui::Condition::FirstUseEver)
+        .opened(&mut open);
+
+    if let Some(usages_window) = window.begin(ui) {
+        let uid = &prefab_usages.prefab_id.to_string();
+        ui.text(im_str!(
+            "Usages of Prefab {}",
+            prefab_name(&prefab_usages.prefab_id)
+        ));
+
+        if prefab_usages.usages.is_empty() {
+            ui.text("Nothing on disk uses it.");
+        }
+
+        let mut last_path = None;
+        for usage in prefab_usages.usages.iter() {
+            if last_path != Some(&usage.path) {
+                ui.bullet_text(&im_str!("{}", usage.path));
+                last_path = Some(&usage.path);
+            }
+
+            let how = match &usage.usage {
+                serialization_util::prefabs::PrefabUsageKind::Instance => "an instance".to_string(),
+                serialization_util::prefabs::PrefabUsageKind::MemberInstance(member) => {
+                    format!("an instance of member {}", member)
+                }
+                serialization_util::prefabs::PrefabUsageKind::Reference(field) => format!("in {}", field),
+            };
+
+            ui.indent();
+            ui.text(im_str!(
+                "{}, {}",
+                Name::get_name_even_quicklier(usage.entity_name.as_ref().map(String::as_str), usage.entity),
+                how
+            ));
+            ui.unindent();
+        }
+
+        if prefab_usages.unchecked_files.is_empty() == false {
+            imgui_system::wrap_style_color_var(
+                ui,
+                imgui::StyleColor::Text,
+                imgui_utility::red_warning_color(),
+                || {
+                    ui.text("We couldn't check these files:");
+                    for unchecked_file in prefab_usages.unchecked_files.iter() {
+                        ui.bullet_text(&im_str!("{}", unchecked_file));
+                    }
+                },
+            );
+        }
+
+        if prefab_usages.deleting {
+            ui.separator();
+
+            if prefab_usages.usages.is_empty() == false {
+                ui.text_wrapped(im_str!(
+                    "Everything above will use the replacement instead. Instances of members \
+                    other than the root get unpacked. The current Scene reloads afterwards."
+                ));
+
+                if let Some(replacement) = imgui_system::select_prefab_entity(
+                    "Replace With",
+                    &prefab_usages.replacement,
+                    uid,
+                    ui,
+                    resources.prefabs(),
+                ) {
+                    prefab_usages.replacement = replacement;
+                }
+            }
+
+            delete = ui.button(&im_str!("Delete##{}", uid), [0.0, 0.0]);
+            ui.same_line(0.0);
+            cancel = ui.button(&im_str!("Cancel##{}", uid), [0.0, 0.0]);
+        }
+
+        usages_window.end(ui);
+    }
+
+    if delete {
+        if let Some(prefab_usages) = ui_handler.prefab_usages.take() {
+            let id = prefab_usages.prefab_id;
+
+            match prefab_usages.delete(resources) {
+                Ok(true) => {
+                    scene_state.set_next_scene(scene_state.current().clone());
+                }
+                Ok(false) => {}
+                Err(e) => error!("Couldn't delete Prefab {}: {}", id, e),
+            }
+        }
+    } else if cancel || open == false {
+        *ui_handler.prefab_usages = None;
+    }
+}
+
 fn display_prefab_id(
     prefab: Uuid,
     name_inspector_params: &NameInspectorParameters,
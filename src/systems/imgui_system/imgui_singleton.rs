@@ -2,6 +2,7 @@ use super::*;
 use std::collections::HashMap;
 
 pub fn singleton_inspector(
+    scene: &Scene,
     sd: &mut SingletonDatabase,
     cd_n: &ComponentList<Name>,
     entities: &[Entity],
@@ -14,30 +15,26 @@ pub fn singleton_inspector(
         .opened(&mut is_open);
 
     if let Some(window) = tileset_viewer_window.begin(&ui_handler.ui) {
-        // @update_singletons
-        inspect_this_singleton_component(
-            &mut sd.camera,
-            &mut sd.associated_entities,
-            cd_n,
-            entities,
-            prefab_hashmap,
-            ui_handler,
-            is_open,
-            |serialized, live| serialized.camera = live.clone(),
-            |serialized, live| *live = serialized.camera,
-        );
+        macro_rules! inspect_singletons {
+            ($($singleton_name:ident: $singleton:ty => $marker:ident,)*) => {
+                $(
+                    inspect_this_singleton_component(
+                        scene,
+                        &mut sd.$singleton_name,
+                        &mut sd.associated_entities,
+                        cd_n,
+                        entities,
+                        prefab_hashmap,
+                        ui_handler,
+                        is_open,
+                        |serialized, live| serialized.$singleton_name = live.clone(),
+                        |serialized, live| *live = serialized.$singleton_name,
+                    );
+                )*
+            };
+        }
+        singleton_registry!(inspect_singletons);
 
-        // inspect_this_singleton_component(
-        //     &mut sd.player,
-        //     &mut sd.associated_entities,
-        //     cd_n,
-        //     entities,
-        //     prefab_hashmap,
-        //     ui_handler,
-        //     is_open,
-        //     |serialized, live| serialized.player = live.clone(),
-        //     |serialized, live| *live = serialized.player.clone(),
-        // );
         window.end(&ui_handler.ui);
     }
 
@@ -45,6 +42,7 @@ pub fn singleton_inspector(
 }
 
 fn inspect_this_singleton_component<T: SingletonBounds, F, F2>(
+    scene: &Scene,
     singleton_component: &mut SingletonComponent<T>,
     associated_entities: &mut HashMap<Marker, Entity>,
     name_list: &ComponentList<Name>,
@@ -99,14 +97,14 @@ fn inspect_this_singleton_component<T: SingletonBounds, F, F2>(
     ui.spacing();
     if ui.button(&im_str!("Serialize##{}", marker_name), [0.0, 0.0]) {
         if let Err(e) =
-            SingletonDatabase::edit_serialized_singleton_database(singleton_component, edit_function)
+            SingletonDatabase::edit_serialized_singleton_database(scene, singleton_component, edit_function)
         {
             error!("Error in Serialization: {}", e);
         }
     }
     ui.same_line(0.0);
     if ui.button(&im_str!("Revert##{}", marker_name), [0.0, 0.0]) {
-        match serialization_util::singleton_components::load_singleton_database() {
+        match serialization_util::singleton_components::load_singleton_database(scene) {
             Ok(scd) => revert_function(scd, singleton_component),
             Err(e) => error!("Error in loading Serialized Singletons {}", e),
         }
@@ -33,6 +33,14 @@ pub fn prefab_blue_color() -> ImColor {
     Color::with_u8(188, 203, 222, 255).into()
 }
 
+/// Components inherited from further up a Prefab chain get more purple the further up they go.
+pub fn prefab_base_color(level: usize) -> ImColor {
+    const PREFAB_BASE_COLORS: [(u8, u8, u8); 3] = [(176, 168, 222), (164, 136, 222), (152, 108, 222)];
+
+    let (r, g, b) = PREFAB_BASE_COLORS[level.saturating_sub(1).min(PREFAB_BASE_COLORS.len() - 1)];
+    Color::with_u8(r, g, b, 255).into()
+}
+
 pub fn typed_text_ui<T: typename::TypeName>() -> String {
     let type_name = T::type_name();
     type_name.split("::").last().unwrap_or(&type_name).to_string()
@@ -183,6 +191,8 @@ pub fn select_entity_option(
     let popup = im_str!("{}## Popup {}", label, uid);
 
     let name_str: ImString = match current_value {
+        // The Entity might have been despawned since it was picked.
+        Some(cv) if entities.contains(cv) == false => im_str!("Despawned Entity ID {}", cv.index()),
         Some(cv) => {
             if let Some(name) = name_list.get(cv) {
                 ImString::new(name.inner().name.clone())
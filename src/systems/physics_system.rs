@@ -1,8 +1,33 @@
 use super::{
-    physics_components::BoundingBox, Axis, ComponentList, Entity, PositionalRect, Rect,
-    Transform, Vec2,
+    physics_components::BoundingBox, Axis, ComponentList, Entity, PositionalRect, Rect, ResourcesDatabase,
+    Sprite, Transform, Vec2,
 };
 
+/// Binds Bounding Boxes to the size of their Sprite, if they ask for it.
+pub fn update_bounding_boxes(
+    bbs: &mut ComponentList<BoundingBox>,
+    sprites: &ComponentList<Sprite>,
+    resources: &ResourcesDatabase,
+) {
+    for this_bb in bbs.iter_mut() {
+        let this_entity_id = this_bb.entity_id();
+        let this_bounding_box: &mut BoundingBox = this_bb.inner_mut();
+
+        if this_bounding_box.bind_to_sprite {
+            if let Some(this_sprite) = sprites.get(&this_entity_id) {
+                if let Some(sprite_name) = &this_sprite.inner().sprite_name {
+                    let sprite_data = resources.sprites.get(sprite_name).unwrap();
+                    let rel_location = sprite_data.origin.sprite_location_relative(sprite_data.size);
+
+                    this_bounding_box.rect = Rect::point_width(rel_location, sprite_data.size.into());
+                };
+            } else {
+                this_bounding_box.bind_to_sprite = false;
+            }
+        }
+    }
+}
+
 // @techdebt. We need a better solution than this!
 pub fn create_positional_rect_lists(
     entity_id: Entity,
@@ -19,7 +44,8 @@ pub fn create_positional_rect_lists(
             .unwrap()
             .inner()
             .world_position();
-        let rect = this_comp.inner().rect;
+        // This has our Transform's scale and rotation in it, which `pos` doesn't.
+        let rect = this_comp.inner().world_rect() - pos;
 
         let this_positional_rec = PositionalRect::new(pos, rect);
 
@@ -1,10 +1,14 @@
-use super::{cardinals::FacingHorizontal, ActionMap, Component, ComponentList, Player, Sprite, Velocity};
+use super::{
+    cardinals::FacingHorizontal, ActionMap, Component, ComponentList, EventChannel, Player, PlayerSwitched,
+    Sprite, Velocity,
+};
 
 pub fn player_update(
     players: &mut ComponentList<Player>,
     sprites: &mut ComponentList<Sprite>,
     velocities: &mut ComponentList<Velocity>,
     action_map: &ActionMap,
+    player_switched: &mut EventChannel<PlayerSwitched>,
 ) {
     let mut active_player: Option<isize> = None;
 
@@ -44,12 +48,17 @@ pub fn player_update(
             } as usize;
 
             // The Players
-            set_player_active(true, players.iter_mut().nth(active_player).unwrap(), sprites);
-            set_player_active(
-                false,
-                players.iter_mut().nth(old_active_player as usize).unwrap(),
-                sprites,
-            );
+            let new_player = players.iter_mut().nth(active_player).unwrap();
+            let to = new_player.entity_id();
+            set_player_active(true, new_player, sprites);
+
+            let old_player = players.iter_mut().nth(old_active_player as usize).unwrap();
+            let from = old_player.entity_id();
+            set_player_active(false, old_player, sprites);
+
+            if from != to {
+                player_switched.send(PlayerSwitched { from, to });
+            }
         }
     } else if let Some(zeroeth) = players.iter_mut().nth(0) {
         info!("No players are active!");
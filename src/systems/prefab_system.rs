@@ -1,12 +1,16 @@
 use super::{
-    serialization_util, Component, ComponentDatabase, Ecs, Entity, Name, Prefab, PrefabLoadRequired,
-    PrefabMap, PrefabMarker, ResourcesDatabase, SerializedComponent, SerializedEntity, SingletonDatabase,
+    serialization_util, Component, ComponentDatabase, Ecs, Entity, Name, NonInspectableEntities, Prefab,
+    PrefabLoadRequired, PrefabMap, PrefabMarker, ResourcesDatabase, Scene, SerializedComponent,
+    SerializedEntity, SingletonDatabase,
 };
 use anyhow::{Context, Result};
 use serde_yaml::Value as YamlValue;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+mod prefab_overrides;
+pub use prefab_overrides::*;
+
 pub fn commit_blank_prefab(resources: &mut ResourcesDatabase) -> Result<uuid::Uuid> {
     let blank_prefab = Prefab::new_blank();
 
@@ -16,7 +20,87 @@ pub fn commit_blank_prefab(resources: &mut ResourcesDatabase) -> Result<uuid::Uu
     Ok(id)
 }
 
+/// Makes a new Prefab which inherits everything from `base_id`, and overrides nothing but
+/// its name. To make a variant out of an instance with overrides, promote the instance.
+pub fn commit_prefab_variant(base_id: Uuid, resources: &mut ResourcesDatabase) -> Result<Uuid> {
+    let base_name = match resources.prefabs().get(&base_id) {
+        Some(base) => Name::get_name_even_quicklier(
+            base.root_entity().name.as_ref().map(|sc| sc.inner.name.as_str()),
+            base_id,
+        ),
+        None => bail!(
+            "Prefab {} doesn't exist, so we can't make a variant of it.",
+            base_id
+        ),
+    };
+
+    let mut variant = Prefab::new_blank();
+    let root_entity = variant.root_entity_mut();
+    root_entity.prefab_marker = Some(SerializedComponent {
+        inner: PrefabMarker::new_main(base_id),
+        active: true,
+    });
+    root_entity.name = Some(SerializedComponent {
+        inner: Name::new(format!("{} Variant", base_name)),
+        active: true,
+    });
+
+    serialization_util::prefabs::serialize_prefab(&variant)?;
+    let id = variant.root_id();
+    resources.add_prefab(variant);
+    Ok(id)
+}
+
+/// Makes a new variant of an instance's Prefab out of the instance. The variant takes the
+/// instance's overrides, and the instance inherits from the variant instead, overriding nothing.
+pub fn commit_instance_as_variant(
+    scene: &Scene,
+    entity: &Entity,
+    component_database: &mut ComponentDatabase,
+    singleton_database: &SingletonDatabase,
+    resources: &mut ResourcesDatabase,
+) -> Result<Uuid> {
+    // The instance's own Serialized Entity is already just its PrefabMarker and its overrides.
+    let root_entity = match SerializedEntity::new(
+        entity,
+        Uuid::new_v4(),
+        component_database,
+        singleton_database,
+        resources,
+    ) {
+        Some(root_entity) if root_entity.prefab_marker.is_some() => root_entity,
+        _ => bail!(
+            "{} isn't a Prefab instance, so it can't become a variant.",
+            Name::get_name_quick(&component_database.names, entity)
+        ),
+    };
+
+    let variant = Prefab::new(root_entity);
+    serialization_util::prefabs::serialize_prefab(&variant)?;
+    let variant_id = variant.root_id();
+    resources.add_prefab(variant);
+
+    component_database
+        .prefab_markers
+        .set_component(entity, PrefabMarker::new_main(variant_id));
+
+    // Like promoting to a Prefab, we serialize again so our overrides move to the variant.
+    if let Some(sc) = component_database.serialization_markers.get(entity) {
+        serialization_util::entities::serialize_entity_full(
+            scene,
+            entity,
+            sc.inner().id,
+            component_database,
+            singleton_database,
+            resources,
+        );
+    }
+
+    Ok(variant_id)
+}
+
 pub fn commit_new_prefab(
+    scene: &Scene,
     entity: &Entity,
     component_database: &mut ComponentDatabase,
     singleton_database: &SingletonDatabase,
@@ -53,6 +137,7 @@ pub fn commit_new_prefab(
         // We do this to remove the "Overrides" that would otherwise appear
         if let Some(sc) = component_database.serialization_markers.get(entity) {
             serialization_util::entities::serialize_entity_full(
+                scene,
                 entity,
                 sc.inner().id,
                 component_database,
@@ -84,7 +169,7 @@ pub fn instantiate_entity_from_prefab(
 
     if let Some(post) = success {
         ecs.component_database
-            .post_deserialization(post, |component_list, sl| {
+            .post_deserialization(post, &mut ecs.scene_graph, |component_list, sl| {
                 if let Some((inner, _)) = component_list.get_mut(&entity) {
                     inner.post_deserialization(entity, sl);
                 }
@@ -115,6 +200,11 @@ pub fn serialize_and_cache_prefab(
         error!("Error Creating Prefab: {}", e);
     }
 
+    cache_prefab(prefab, sub_id, resources)
+}
+
+/// Caches a prefab which has already been serialized.
+pub fn cache_prefab(prefab: Prefab, sub_id: Uuid, resources: &mut ResourcesDatabase) -> PrefabLoadRequired {
     let main_id = prefab.root_id();
 
     match serialization_util::prefabs::cycle_prefab(prefab) {
@@ -127,88 +217,335 @@ pub fn serialize_and_cache_prefab(
     PrefabLoadRequired { main_id, sub_id }
 }
 
+/// An "Apply To Prefab" which is waiting for the editor to confirm it. Applying takes
+/// the override at `path` away from every other inheritor which has one, in every Scene
+/// and Prefab on disk, so first we find them all and show them as a dry run.
+///
+/// The `path` is a whole Component's key, like `sprite`, or one field in it, like
+/// `sprite.inner.sprite_name`. The `value` is what goes there.
+pub struct PrefabPropagation {
+    pub main_id: Uuid,
+    pub sub_id: Uuid,
+    pub path: String,
+    pub value: YamlValue,
+    pub report: serialization_util::prefabs::PropagationReport,
+}
+
+impl PrefabPropagation {
+    pub fn dry_run(main_id: Uuid, sub_id: Uuid, path: String, value: YamlValue) -> Result<Self> {
+        let report = serialization_util::prefabs::find_overriding_inheritors(
+            &PrefabMarker::new(main_id, sub_id),
+            &path,
+        )?;
+
+        Ok(Self {
+            main_id,
+            sub_id,
+            path,
+            value,
+            report,
+        })
+    }
+
+    pub fn apply(self, ecs: &mut Ecs, resources: &mut ResourcesDatabase) -> Result<()> {
+        let mut prefab = serialization_util::prefabs::load_prefab(&self.main_id)?
+            .ok_or_else(|| format_err!("We couldn't load Prefab {} to apply to it.", self.main_id))?;
+
+        let member = prefab
+            .members
+            .get(&self.sub_id)
+            .cloned()
+            .ok_or_else(|| format_err!("Prefab {} has no member {}.", self.main_id, self.sub_id))?;
+
+        // A member which is a variant, or a nested instance, only holds its overrides,
+        // so we set the path on what it inherits and then take the inherited parts back out.
+        let base = match &member.prefab_marker {
+            Some(pm) => Some(resolve_prefab_member(
+                resources.prefabs(),
+                pm.inner.main_id(),
+                pm.inner.sub_id(),
+            )?),
+            None => None,
+        };
+
+        let mut member_yaml = match &base {
+            Some(base) => serde_yaml::to_value(load_override_into_prefab(base.clone(), member)?)?,
+            None => serde_yaml::to_value(member)?,
+        };
+
+        let key = path_component(&self.path);
+        if self.path != key && get_path(&member_yaml, key).map_or(true, YamlValue::is_null) {
+            bail!(
+                "Prefab {} doesn't have a {} to set {} in. Apply the whole Component instead.",
+                self.main_id,
+                key,
+                self.path
+            );
+        }
+        set_path(&mut member_yaml, &self.path, self.value.clone());
+
+        let mut new_member: SerializedEntity = serde_yaml::from_value(member_yaml)?;
+        if let Some(base) = &base {
+            new_member = strip_inherited(new_member, base)?;
+        }
+        prefab.members.insert(new_member.id, new_member);
+
+        // If the Prefab won't save, nothing else should change to match it.
+        serialization_util::prefabs::serialize_prefab(&prefab)?;
+        let prefab_reload_required = cache_prefab(prefab, self.sub_id, resources);
+        let new_base = resolve_prefab_member(resources.prefabs(), self.main_id, self.sub_id)?;
+
+        // Everything else on disk, including Prefabs which inherit from this one
+        let dropped_overrides = serialization_util::prefabs::drop_overrides(
+            &self.report,
+            &PrefabMarker::new(self.main_id, self.sub_id),
+            &new_base,
+            &self.path,
+        );
+        for changed_prefab in dropped_overrides.changed_prefabs {
+            resources.add_prefab(changed_prefab);
+        }
+        for written_file in dropped_overrides.written_files.iter() {
+            info!("Dropped the override of {} in {}", self.path, written_file);
+        }
+
+        // And then what's live
+        post_prefab_serialization(ecs, resources, &self.path, self.value, prefab_reload_required)?;
+
+        if dropped_overrides.failed_files.is_empty() == false {
+            bail!(
+                "We applied {} to the Prefab, but these files still override it:\n{}",
+                self.path,
+                dropped_overrides.failed_files.join("\n")
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// What uses a Prefab, for the editor's "Find Usages" view. We show it before deleting a
+/// Prefab too, since anything still using it would break, so it needs a `replacement` first.
+pub struct PrefabUsages {
+    pub prefab_id: Uuid,
+    pub usages: Vec<serialization_util::prefabs::PrefabUsage>,
+    /// Files we couldn't check. Why, for each.
+    pub unchecked_files: Vec<String>,
+    pub deleting: bool,
+    pub replacement: Option<Uuid>,
+}
+
+impl PrefabUsages {
+    pub fn find(prefab_id: Uuid, deleting: bool) -> Result<Self> {
+        let mut dependencies = serialization_util::prefabs::PrefabDependencies::build()?;
+
+        Ok(Self {
+            prefab_id,
+            usages: dependencies.usages.remove(&prefab_id).unwrap_or_default(),
+            unchecked_files: dependencies.unchecked_files,
+            deleting,
+            replacement: None,
+        })
+    }
+
+    /// Whether we know nothing would break if the Prefab went away.
+    pub fn is_unused(&self) -> bool {
+        self.usages.is_empty() && self.unchecked_files.is_empty()
+    }
+
+    /// Deletes the Prefab, once everything which used it uses the replacement instead.
+    /// Returns whether anything was replaced, since the current Scene might need a reload.
+    pub fn delete(self, resources: &mut ResourcesDatabase) -> Result<bool> {
+        if self.unchecked_files.is_empty() == false {
+            bail!(
+                "We couldn't check every file for usages of Prefab {}, so we won't delete it.",
+                self.prefab_id
+            );
+        }
+
+        let replaced = self.usages.is_empty() == false;
+        if replaced {
+            let replacement = match self.replacement {
+                Some(replacement) => replacement,
+                None => bail!(
+                    "Prefab {} is used in {} places. Pick a replacement for it first.",
+                    self.prefab_id,
+                    self.usages.len()
+                ),
+            };
+
+            // Anything which inherits from the Prefab would end up inheriting from itself
+            let inherits_deleted = prefab_chain(resources.prefabs(), replacement, replacement)?
+                .iter()
+                .any(|(pm, _)| pm.main_id() == self.prefab_id);
+            if inherits_deleted {
+                bail!(
+                    "Prefab {} inherits from Prefab {}, so it can't replace it.",
+                    replacement,
+                    self.prefab_id
+                );
+            }
+
+            let changed_prefabs = serialization_util::prefabs::replace_prefab(
+                &self.usages,
+                self.prefab_id,
+                replacement,
+                resources.prefabs(),
+            )?;
+            for changed_prefab in changed_prefabs {
+                resources.add_prefab(changed_prefab);
+            }
+        }
+
+        let mut prefab = resources
+            .remove_prefab(&self.prefab_id)
+            .ok_or_else(|| format_err!("Prefab {} doesn't exist, so we can't delete it.", self.prefab_id))?;
+        prefab.invalidate();
+
+        serialization_util::prefabs::invalidate_prefab(&prefab)
+            .with_context(|| format!("We couldn't invalidate Prefab {}", self.prefab_id))?;
+
+        Ok(replaced)
+    }
+}
+
 /// Use this to finish a prefab serialization. This is a fairly huge operation,
 /// so be careful with it.
+///
+/// Every live inheritor gets `value` put at `path`. Inheritors further down the chain,
+/// like instances of a variant of the Prefab, get it too, unless something nearer to
+/// them overrides it.
 pub fn post_prefab_serialization(
     ecs: &mut Ecs,
-    key: serde_yaml::Value,
-    delta: serde_yaml::Value,
+    resources: &ResourcesDatabase,
+    path: &str,
+    value: serde_yaml::Value,
     prefab_load: PrefabLoadRequired,
 ) -> Result<()> {
+    let key_name = path_component(path);
+    let key = YamlValue::from(key_name);
     let PrefabLoadRequired { main_id, sub_id } = prefab_load;
+    let changed_member = PrefabMarker::new(main_id, sub_id);
     let mut post_deserialization = None;
     let mut entities_to_post_deserialize = vec![];
+    let mut direct_inheritors = vec![];
+
+    let scene = ecs.scene_state.current();
+    let mut serialized_entities: HashMap<Uuid, SerializedEntity> =
+        serialization_util::entities::load_all_entities(scene)
+            .with_context(|| format!("We couldn't load Scene {}.", scene.name()))?;
 
     for entity in ecs.entities.iter() {
-        if ecs
+        let prefab_marker = match ecs.component_database.prefab_markers.get(entity) {
+            Some(pmc) => pmc.inner().clone(),
+            None => continue,
+        };
+        let serialized_id = ecs
             .component_database
-            .prefab_markers
+            .serialization_markers
             .get(entity)
-            .map(|pmc| {
-                let pm = pmc.inner();
-                pm.main_id() == main_id && pm.sub_id() == sub_id
-            })
-            .unwrap_or_default()
-        {
-            // Load the Delta into each existing Prefab inheritor
-            let new_post = ecs.component_database.load_yaml_delta_into_database(
-                entity,
-                key.clone(),
-                delta.clone(),
-                Default::default(),
-                &mut ecs.singleton_database.associated_entities,
-            );
+            .map(|se| se.inner().id);
+
+        let level = match prefab_chain(
+            resources.prefabs(),
+            prefab_marker.main_id(),
+            prefab_marker.sub_id(),
+        ) {
+            Ok(chain) => chain
+                .iter()
+                .position(|(pm, _)| pm == &changed_member)
+                .filter(|level| {
+                    chain[..*level]
+                        .iter()
+                        .all(|(_, member)| overrides_path(member, path) == false)
+                }),
+            Err(e) => {
+                error!("{}", e);
+                continue;
+            }
+        };
+
+        match level {
+            // Direct inheritors take the change, and drop their override of it below.
+            Some(0) => direct_inheritors.push(serialized_id),
+            // Further down the chain, the Scene's overrides still win.
+            Some(_) => {
+                let overridden = serialized_id
+                    .and_then(|id| serialized_entities.get(&id))
+                    .map_or(false, |se| overrides_path(se, path));
+
+                if overridden {
+                    continue;
+                }
+            }
+            None => continue,
+        }
 
-            // Reload the serialization after the fact
-            post_deserialization = Some(new_post);
-            entities_to_post_deserialize.push((
-                *entity,
-                ecs.component_database
-                    .serialization_markers
-                    .get(entity)
-                    .map(|se| se.inner().id),
-            ));
+        // Put the value into what the inheritor has now, which might be nothing at all
+        // if it's missing the Component, and load that back in as the Delta.
+        let mut live_component = YamlValue::Null;
+        ecs.component_database
+            .foreach_component_list(NonInspectableEntities::all(), |component_list| {
+                if component_list.get_yaml_component_key() == key {
+                    live_component = component_list.create_yaml_component(entity);
+                }
+            });
+        if live_component.is_null() && key_name != path {
+            match resolve_prefab_member(resources.prefabs(), main_id, sub_id) {
+                Ok(member) => {
+                    live_component = get_path(&serde_yaml::to_value(member)?, key_name)
+                        .cloned()
+                        .unwrap_or_default();
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    continue;
+                }
+            }
         }
+
+        let mut delta = YamlValue::Mapping(Default::default());
+        set_path(&mut delta, key_name, live_component);
+        set_path(&mut delta, path, value.clone());
+
+        let new_post = ecs.component_database.load_yaml_delta_into_database(
+            entity,
+            key.clone(),
+            delta[&key].clone(),
+            Default::default(),
+            &mut ecs.singleton_database.associated_entities,
+        );
+
+        // Reload the serialization after the fact
+        post_deserialization = Some(new_post);
+        entities_to_post_deserialize.push(*entity);
     }
 
     if let Some(pd) = post_deserialization {
         ecs.component_database
-            .post_deserialization(pd, |component_list, sl| {
-                for (entity, _) in entities_to_post_deserialize.iter_mut() {
+            .post_deserialization(pd, &mut ecs.scene_graph, |component_list, sl| {
+                for entity in entities_to_post_deserialize.iter_mut() {
                     if let Some((inner, _)) = component_list.get_mut(&entity) {
                         inner.post_deserialization(*entity, sl);
                     }
                 }
             });
-        let serialized_entities: HashMap<Uuid, SerializedEntity> =
-            serialization_util::entities::load_all_entities().with_context(|| {
-                format!(
-                    "We couldn't load Scene {}.",
-                    super::scene_system::current_scene_name()
-                )
-            })?;
-
-        let new_serialized_entities: HashMap<Uuid, SerializedEntity> = {
-            let mut serialized_entities_value: YamlValue = serde_yaml::to_value(serialized_entities).unwrap();
-
-            let serialized_entities_map = serialized_entities_value.as_mapping_mut().unwrap();
-            for (_, id) in entities_to_post_deserialize {
-                // find the entity in the Hashmap
-                let id_key = serde_yaml::to_value(id).unwrap();
+    }
 
-                // Find the key...
-                if let Some(serialized_entity) = serialized_entities_map.get_mut(&id_key) {
-                    let entity_as_map = serialized_entity.as_mapping_mut().unwrap();
-                    // And put a null in it! in the future, we'll be removing it, but for now
-                    // we can do that until we switch over to purely dynamic typed saves
-                    entity_as_map.insert(key.clone(), YamlValue::Null);
-                }
+    // Direct inheritors in the Scene file drop their override of the path
+    let mut dropped_override = false;
+    for id in direct_inheritors.into_iter().flatten() {
+        if let Some(serialized_entity) = serialized_entities.get_mut(&id) {
+            if overrides_path(serialized_entity, path) {
+                let base = resolve_prefab_member(resources.prefabs(), main_id, sub_id)?;
+                drop_override(serialized_entity, &base, path)?;
+                dropped_override = true;
             }
+        }
+    }
 
-            serde_yaml::from_value(serialized_entities_value).unwrap()
-        };
-
-        serialization_util::entities::commit_all_entities(&new_serialized_entities)?;
+    if dropped_override {
+        serialization_util::entities::commit_all_entities(scene, &serialized_entities)?;
     }
 
     Ok(())
@@ -219,6 +556,9 @@ pub fn post_prefab_serialization(
 /// Actor. If Player's entity was passed into this method,
 /// a Serialized Actor would come out.
 ///
+/// If Actor is itself a variant of another Prefab, or a Prefab nested inside one,
+/// what comes out is the whole chain, resolved.
+///
 /// Returns a **flag** indicating if a prefab was found,
 /// which will have been loaded into the SerializedEntity provided.
 pub fn get_serialized_parent_prefab_from_inheritor(
@@ -227,20 +567,24 @@ pub fn get_serialized_parent_prefab_from_inheritor(
     serialized_entity: &mut SerializedEntity,
 ) -> bool {
     if let Some(prefab_component) = maybe_prefab_marker {
-        let prefab = match resources.prefabs().get(&prefab_component.inner().main_id()) {
-            Some(i) => i,
-            None => return false,
-        };
-
-        let mut serialized_prefab = match prefab.members.get(&prefab_component.inner().sub_id()) {
-            Some(sp) => sp.clone(),
-            None => return false,
+        let prefab_marker = prefab_component.inner();
+        let mut serialized_prefab = match resolve_prefab_member(
+            resources.prefabs(),
+            prefab_marker.main_id(),
+            prefab_marker.sub_id(),
+        ) {
+            Ok(sp) => sp,
+            Err(e) => {
+                error!("{}", e);
+                return false;
+            }
         };
 
         serialized_prefab.prefab_marker = Some(SerializedComponent {
             active: true,
-            inner: prefab_component.inner().clone(),
+            inner: prefab_marker.clone(),
         });
+        serialized_prefab.prefab_overrides.clear();
 
         *serialized_entity = serialized_prefab;
         true
@@ -249,21 +593,100 @@ pub fn get_serialized_parent_prefab_from_inheritor(
     }
 }
 
+/// Every Prefab member an inheritor of `main_id`/`sub_id` gets its components from,
+/// nearest first. A member with a PrefabMarker of its own is a variant of (or a nested
+/// instance of) whatever that marker points at, and only holds its overrides.
+pub fn prefab_chain(
+    prefabs: &PrefabMap,
+    main_id: Uuid,
+    sub_id: Uuid,
+) -> Result<Vec<(PrefabMarker, &SerializedEntity)>> {
+    let mut chain: Vec<(PrefabMarker, &SerializedEntity)> = vec![];
+    let mut prefab_marker = PrefabMarker::new(main_id, sub_id);
+
+    loop {
+        if chain.iter().any(|(pm, _)| pm == &prefab_marker) {
+            bail!(
+                "Prefab {} inherits from itself! Check the PrefabMarkers of its members.",
+                main_id
+            );
+        }
+
+        let member = prefabs
+            .get(&prefab_marker.main_id())
+            .and_then(|prefab| prefab.members.get(&prefab_marker.sub_id()))
+            .ok_or_else(|| {
+                format_err!(
+                    "Prefab {} has no member {}, but something inherits from it.",
+                    prefab_marker.main_id(),
+                    prefab_marker.sub_id()
+                )
+            })?;
+
+        let base = member.prefab_marker.as_ref().map(|pm| pm.inner.clone());
+        chain.push((prefab_marker, member));
+
+        match base {
+            Some(base) => prefab_marker = base,
+            None => return Ok(chain),
+        }
+    }
+}
+
+/// Flattens a Prefab member and everything it inherits from into one SerializedEntity.
+/// Its `prefab_marker` is left pointing at the member's own base, if it has one.
+pub fn resolve_prefab_member(prefabs: &PrefabMap, main_id: Uuid, sub_id: Uuid) -> Result<SerializedEntity> {
+    let chain = prefab_chain(prefabs, main_id, sub_id)?;
+    let mut levels = chain.into_iter().rev();
+
+    let mut resolved = levels.next().unwrap().1.clone();
+    for (_, member) in levels {
+        resolved = load_override_into_prefab(resolved, member.clone())?;
+    }
+
+    Ok(resolved)
+}
+
 /// This uses the *experimental* idea of some dynamic typings in YAML! These unwraps *should*
 /// be safe, as we know that SerializedEntity can be safely serialized and deserialized.
+///
+/// Components with fields listed in the override's `prefab_overrides` only replace those
+/// fields, so the rest of the Component still comes from the Prefab.
 pub fn load_override_into_prefab(
     prefab_serialized_entity: SerializedEntity,
     se_override: SerializedEntity,
 ) -> Result<SerializedEntity> {
     let mut prefab_serialized_yaml = serde_yaml::to_value(prefab_serialized_entity).unwrap();
-    let se_override_yaml = serde_yaml::to_value(se_override).unwrap();
+    let se_override_yaml = serde_yaml::to_value(&se_override).unwrap();
 
-    let prefab_serialized_value_as_map = prefab_serialized_yaml.as_mapping_mut().unwrap();
-
-    if let YamlValue::Mapping(mapping) = se_override_yaml {
+    if let YamlValue::Mapping(mapping) = &se_override_yaml {
         for (key, value) in mapping {
-            if value != serde_yaml::Value::Null {
-                prefab_serialized_value_as_map.insert(key, value);
+            if value == &serde_yaml::Value::Null {
+                continue;
+            }
+
+            let overridden_fields: Vec<&String> = key
+                .as_str()
+                .map(|key| {
+                    se_override
+                        .prefab_overrides
+                        .iter()
+                        .filter(|path| path_component(path) == key)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            if overridden_fields.is_empty() || prefab_serialized_yaml[key].is_null() {
+                prefab_serialized_yaml
+                    .as_mapping_mut()
+                    .unwrap()
+                    .insert(key.clone(), value.clone());
+            } else {
+                for path in overridden_fields {
+                    if let Some(field) = get_path(&se_override_yaml, path) {
+                        set_path(&mut prefab_serialized_yaml, path, field.clone());
+                    }
+                }
             }
         }
     }
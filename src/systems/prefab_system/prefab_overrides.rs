@@ -0,0 +1,356 @@
+use super::*;
+use serde_yaml::Mapping;
+
+/// The keys of a SerializedEntity which aren't Components, so never show up in an override path.
+const NOT_COMPONENTS: [&str; 4] = ["id", "marker", "prefab_marker", "prefab_overrides"];
+
+/// The Component a path is in, so `sprite` for `sprite.inner.sprite_name`.
+pub fn path_component(path: &str) -> &str {
+    path.split('.').next().unwrap_or_default()
+}
+
+/// Whether one path is the other, or is somewhere inside of it.
+pub fn paths_overlap(lhs: &str, rhs: &str) -> bool {
+    let inside = |inner: &str, outer: &str| inner.starts_with(outer) && inner[outer.len()..].starts_with('.');
+
+    lhs == rhs || inside(lhs, rhs) || inside(rhs, lhs)
+}
+
+/// The value at `path` in some YAML, like a whole SerializedEntity, if it's there.
+pub fn get_path<'a>(yaml: &'a YamlValue, path: &str) -> Option<&'a YamlValue> {
+    path.split('.').try_fold(yaml, |value, field| {
+        value.as_mapping()?.get(&YamlValue::from(field))
+    })
+}
+
+/// Puts `new_value` at `path`, making any mappings it needs to get there.
+pub fn set_path(yaml: &mut YamlValue, path: &str, new_value: YamlValue) {
+    let mut value = yaml;
+
+    for field in path.split('.') {
+        if value.is_mapping() == false {
+            *value = YamlValue::Mapping(Mapping::new());
+        }
+
+        let mapping = value.as_mapping_mut().unwrap();
+        let field = YamlValue::from(field);
+        if mapping.contains_key(&field) == false {
+            mapping.insert(field.clone(), YamlValue::Null);
+        }
+        value = mapping.get_mut(&field).unwrap();
+    }
+
+    *value = new_value;
+}
+
+/// Every path, starting at `path`, where `component` differs from `base`. We go into mappings
+/// field by field, but anything else (like a list) counts as one field.
+pub fn changed_paths(path: &str, base: &YamlValue, component: &YamlValue) -> Vec<String> {
+    let mut changed = vec![];
+    collect_changed_paths(path.to_string(), base, component, &mut changed);
+    changed
+}
+
+fn collect_changed_paths(path: String, base: &YamlValue, component: &YamlValue, changed: &mut Vec<String>) {
+    if base == component {
+        return;
+    }
+
+    match (base, component) {
+        (YamlValue::Mapping(base), YamlValue::Mapping(component))
+            if base.len() == component.len()
+                && component
+                    .iter()
+                    .all(|(field, _)| field.is_string() && base.contains_key(field)) =>
+        {
+            for (field, value) in component.iter() {
+                let field_path = format!("{}.{}", path, field.as_str().unwrap());
+                collect_changed_paths(field_path, &base[field], value, changed);
+            }
+        }
+        _ => changed.push(path),
+    }
+}
+
+/// The paths a Prefab inheritor overrides, found by comparing it to the Prefab it inherits.
+/// Components the Prefab doesn't have at all aren't listed, since they're overridden whole.
+pub fn find_prefab_overrides(serialized_entity: &SerializedEntity, prefab: &SerializedEntity) -> Vec<String> {
+    let mut overrides = vec![];
+
+    if let (Ok(serialized_entity), Ok(prefab)) = (
+        serde_yaml::to_value(serialized_entity),
+        serde_yaml::to_value(prefab),
+    ) {
+        for key in component_keys(&serialized_entity) {
+            let component = &serialized_entity[key];
+            let base = &prefab[key];
+
+            if component.is_null() == false && base.is_null() == false {
+                overrides.extend(changed_paths(key, base, component));
+            }
+        }
+    }
+
+    overrides.sort();
+    overrides
+}
+
+/// Lists again which fields of the Component under `key` differ from the `prefab`'s, for
+/// when that's the only Component which changed.
+pub fn refresh_prefab_overrides(
+    serialized_entity: &mut SerializedEntity,
+    prefab: &SerializedEntity,
+    key: &str,
+) {
+    serialized_entity
+        .prefab_overrides
+        .retain(|path| path_component(path) != key);
+
+    if let (Ok(serialized_yaml), Ok(prefab_yaml)) = (
+        serde_yaml::to_value(&*serialized_entity),
+        serde_yaml::to_value(prefab),
+    ) {
+        let (component, base) = (&serialized_yaml[key], &prefab_yaml[key]);
+
+        if component.is_null() == false && base.is_null() == false {
+            serialized_entity
+                .prefab_overrides
+                .extend(changed_paths(key, base, component));
+            serialized_entity.prefab_overrides.sort();
+        }
+    }
+}
+
+/// Nulls out every Component which is the same as the `base`'s, and lists the fields
+/// which are left over, so the SerializedEntity holds only what it overrides.
+pub fn strip_inherited(
+    serialized_entity: SerializedEntity,
+    base: &SerializedEntity,
+) -> Result<SerializedEntity> {
+    let mut serialized_yaml = serde_yaml::to_value(serialized_entity)?;
+    let base_yaml = serde_yaml::to_value(base)?;
+
+    let inherited: Vec<String> = component_keys(&serialized_yaml)
+        .filter(|key| serialized_yaml[*key] == base_yaml[*key])
+        .map(|key| key.to_string())
+        .collect();
+
+    for key in inherited {
+        set_path(&mut serialized_yaml, &key, YamlValue::Null);
+    }
+
+    let mut serialized_entity: SerializedEntity = serde_yaml::from_value(serialized_yaml)?;
+    serialized_entity.prefab_overrides = find_prefab_overrides(&serialized_entity, base);
+    Ok(serialized_entity)
+}
+
+/// Fills out each Component a SerializedEntity overrides only some fields of with the rest
+/// of its fields from `base`. Every other Component is left as it is.
+pub fn fill_overridden_components(
+    serialized_entity: SerializedEntity,
+    base: SerializedEntity,
+) -> Result<SerializedEntity> {
+    let mut serialized_yaml = serde_yaml::to_value(&serialized_entity)?;
+    let filled_yaml = serde_yaml::to_value(load_override_into_prefab(base, serialized_entity.clone())?)?;
+
+    for path in serialized_entity.prefab_overrides.iter() {
+        let key = path_component(path);
+        set_path(&mut serialized_yaml, key, filled_yaml[key].clone());
+    }
+
+    Ok(serde_yaml::from_value(serialized_yaml)?)
+}
+
+fn component_keys(serialized_entity: &YamlValue) -> impl Iterator<Item = &str> {
+    serialized_entity
+        .as_mapping()
+        .into_iter()
+        .flat_map(|mapping| mapping.iter())
+        .filter_map(|(key, _)| key.as_str())
+        .filter(|key| NOT_COMPONENTS.contains(key) == false)
+}
+
+fn component_overrides<'a>(
+    serialized_entity: &'a SerializedEntity,
+    key: &'a str,
+) -> impl Iterator<Item = &'a String> {
+    serialized_entity
+        .prefab_overrides
+        .iter()
+        .filter(move |path| path_component(path) == key)
+}
+
+/// Whether a SerializedEntity has its own value at `path`. A Component without any
+/// `prefab_overrides` in it counts as overriding every path inside it.
+pub fn overrides_path(serialized_entity: &SerializedEntity, path: &str) -> bool {
+    let key = path_component(path);
+    let has_component = serde_yaml::to_value(serialized_entity)
+        .ok()
+        .and_then(|se| get_path(&se, key).map(|component| component.is_null() == false))
+        .unwrap_or_default();
+
+    if has_component == false {
+        return false;
+    }
+
+    let mut paths = component_overrides(serialized_entity, key).peekable();
+    paths.peek().is_none() || paths.any(|overridden| paths_overlap(overridden, path))
+}
+
+/// Takes a SerializedEntity's override at `path` away, so it inherits it again from `base`,
+/// the Prefab member it inherits. If that was the last field it overrode in the Component,
+/// the Component goes too. A Component overridden whole first lists the fields in it which
+/// differ from `base`, so only the one at `path` is lost.
+pub fn drop_override(
+    serialized_entity: &mut SerializedEntity,
+    base: &SerializedEntity,
+    path: &str,
+) -> Result<()> {
+    let key = path_component(path).to_string();
+    let whole = component_overrides(serialized_entity, &key).next().is_none();
+
+    if whole && path != key {
+        let serialized_yaml = serde_yaml::to_value(&*serialized_entity)?;
+        let base_yaml = serde_yaml::to_value(base)?;
+
+        if base_yaml[key.as_str()].is_null() == false {
+            serialized_entity.prefab_overrides.extend(changed_paths(
+                &key,
+                &base_yaml[key.as_str()],
+                &serialized_yaml[key.as_str()],
+            ));
+            serialized_entity.prefab_overrides.sort();
+        }
+    }
+
+    serialized_entity
+        .prefab_overrides
+        .retain(|overridden| paths_overlap(overridden, path) == false);
+
+    if component_overrides(serialized_entity, &key).next().is_none() {
+        let mut serialized_yaml = serde_yaml::to_value(&*serialized_entity)?;
+        set_path(&mut serialized_yaml, &key, YamlValue::Null);
+        *serialized_entity = serde_yaml::from_value(serialized_yaml)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> YamlValue {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    fn named_entity(prefab_overrides: &[&str]) -> SerializedEntity {
+        SerializedEntity {
+            name: Some(SerializedComponent {
+                inner: Name::new("Box".to_string()),
+                active: true,
+            }),
+            prefab_overrides: prefab_overrides.iter().map(|path| path.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn paths_overlap_inside_each_other() {
+        assert!(paths_overlap("sprite", "sprite"));
+        assert!(paths_overlap("sprite", "sprite.inner.sprite_name"));
+        assert!(paths_overlap("sprite.inner.sprite_name", "sprite"));
+
+        assert!(paths_overlap("sprite.inner.sprite_name", "sprite.inner.tint") == false);
+        // A shared prefix isn't enough. It has to be a whole field.
+        assert!(paths_overlap("sprite", "sprite_sheet") == false);
+    }
+
+    #[test]
+    fn get_path_finds_nested_fields() {
+        let component = yaml("inner: {sprite_name: box, size: {x: 1}}");
+
+        assert_eq!(get_path(&component, "inner.sprite_name"), Some(&yaml("box")));
+        assert_eq!(get_path(&component, "inner.size.x"), Some(&yaml("1")));
+        assert_eq!(get_path(&component, "inner.tint"), None);
+        assert_eq!(get_path(&component, "inner.sprite_name.x"), None);
+    }
+
+    #[test]
+    fn set_path_makes_what_it_needs() {
+        let mut component = yaml("inner: {sprite_name: box}");
+
+        set_path(&mut component, "inner.sprite_name", yaml("crate"));
+        assert_eq!(component, yaml("inner: {sprite_name: crate}"));
+
+        set_path(&mut component, "inner.size.x", yaml("2"));
+        assert_eq!(component, yaml("inner: {sprite_name: crate, size: {x: 2}}"));
+
+        // Anything in the way which isn't a mapping gets replaced by one.
+        set_path(&mut component, "inner.sprite_name.x", yaml("3"));
+        assert_eq!(component, yaml("inner: {sprite_name: {x: 3}, size: {x: 2}}"));
+    }
+
+    #[test]
+    fn changed_paths_goes_field_by_field() {
+        let base = yaml("inner: {sprite_name: box, size: {x: 1, y: 1}, frames: [1, 2]}");
+        let component = yaml("inner: {sprite_name: box, size: {x: 2, y: 1}, frames: [1, 3]}");
+
+        assert_eq!(
+            changed_paths("sprite", &base, &component),
+            vec!["sprite.inner.size.x", "sprite.inner.frames"]
+        );
+        assert!(changed_paths("sprite", &base, &base).is_empty());
+
+        // A mapping with different fields changed as a whole.
+        let component = yaml("inner: {sprite_name: box, size: {x: 1}, frames: [1, 2]}");
+        assert_eq!(
+            changed_paths("sprite", &base, &component),
+            vec!["sprite.inner.size"]
+        );
+    }
+
+    #[test]
+    fn drop_override_keeps_the_other_fields() {
+        let mut serialized_entity = named_entity(&["name.active", "name.inner.name"]);
+        drop_override(&mut serialized_entity, &named_entity(&[]), "name.inner.name").unwrap();
+
+        assert!(serialized_entity.name.is_some());
+        assert_eq!(serialized_entity.prefab_overrides, vec!["name.active"]);
+    }
+
+    #[test]
+    fn drop_override_drops_the_component_with_its_last_field() {
+        let mut serialized_entity = named_entity(&["name.inner.name"]);
+        drop_override(&mut serialized_entity, &named_entity(&[]), "name.inner.name").unwrap();
+
+        assert!(serialized_entity.name.is_none());
+        assert!(serialized_entity.prefab_overrides.is_empty());
+    }
+
+    #[test]
+    fn drop_override_lists_the_rest_of_a_whole_component() {
+        // Without any paths, the Name is overridden whole, but only two fields differ.
+        let mut base = named_entity(&[]);
+        base.name.as_mut().unwrap().active = false;
+        base.name.as_mut().unwrap().inner = Name::new("Crate".to_string());
+
+        let mut serialized_entity = named_entity(&[]);
+        drop_override(&mut serialized_entity, &base, "name.inner.name").unwrap();
+        assert!(serialized_entity.name.is_some());
+        assert_eq!(serialized_entity.prefab_overrides, vec!["name.active"]);
+
+        // If nothing else differs, the Component goes.
+        let mut serialized_entity = named_entity(&[]);
+        drop_override(&mut serialized_entity, &named_entity(&[]), "name.inner.name").unwrap();
+        assert!(serialized_entity.name.is_none());
+    }
+
+    #[test]
+    fn drop_override_drops_a_whole_component() {
+        let mut serialized_entity = named_entity(&["name.active", "name.inner.name"]);
+        drop_override(&mut serialized_entity, &named_entity(&[]), "name").unwrap();
+        assert!(serialized_entity.name.is_none());
+        assert!(serialized_entity.prefab_overrides.is_empty());
+    }
+}
@@ -9,4 +9,4 @@ mod utilities;
 pub use core_draw::render;
 pub use draw_imgui::initialize_imgui;
 pub use pre_draw::pre_draw;
-pub use utilities::register_texture;
+pub use utilities::{register_texture, replace_texture};
@@ -86,7 +86,7 @@ fn draw<'a>(
             );
 
             // DRAW THE GAME
-            if let Some(game_world_commands) = draw_commands.game_world.take() {
+            for game_world_commands in draw_commands.game_worlds.drain(..) {
                 draw_game_world::draw_game_world(
                     cmd_buffer,
                     game_world_commands,
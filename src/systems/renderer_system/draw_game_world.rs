@@ -72,7 +72,7 @@ pub(super) unsafe fn draw_game_world<'a>(
         if let Some(transform) = transforms.get(&this_sprite.entity_id()) {
             quad_buffer.push(sprite_system::to_standard_quad(
                 this_sprite.inner(),
-                transform.inner().world_position(),
+                &transform.inner().world_transform(),
                 resources,
             ));
         }
@@ -84,7 +84,7 @@ pub(super) unsafe fn draw_game_world<'a>(
                 quad_buffer.push(
                     text_source
                         .inner()
-                        .prepare_standard_quad(transform.inner().world_position(), quad),
+                        .prepare_standard_quad(&transform.inner().world_transform(), quad),
                 );
             }
         }
@@ -95,7 +95,7 @@ pub(super) unsafe fn draw_game_world<'a>(
             quad_buffer.push(
                 this_draw_rect
                     .inner()
-                    .to_standard_quad(transform.inner().world_position()),
+                    .to_standard_quad(&transform.inner().world_transform()),
             );
         }
     }
@@ -103,7 +103,7 @@ pub(super) unsafe fn draw_game_world<'a>(
     //     if let Some(transform) = transforms.get(&this_tilemap.entity_id()) {
     //         this_tilemap
     //             .inner()
-    //             .create_tile_quads(transform.inner().world_position(), quad_buffer);
+    //             .create_tile_quads(&transform.inner().world_transform(), quad_buffer);
     //     }
     // }
 
@@ -142,6 +142,7 @@ fn build_text(
                         );
 
                         StandardQuad {
+                            transform: super::Affine2::IDENTITY,
                             pos,
                             image_size,
                             texture_info: TextureDescription::Standard(standard_tex),
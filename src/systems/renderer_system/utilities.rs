@@ -34,6 +34,28 @@ pub fn register_texture(renderer: &mut RC, image: &RgbaImage) -> Result<usize, E
     Ok(ret)
 }
 
+/// Uploads `image` over an already registered texture page. We wait for the device
+/// to go idle first, since frames in flight might still be sampling the old image.
+pub fn replace_texture(renderer: &mut RC, texture_page: usize, image: &RgbaImage) -> Result<(), Error> {
+    renderer
+        .device
+        .wait_idle()
+        .map_err(|e| format_err!("Couldn't wait for the GPU before replacing a texture: {:?}", e))?;
+
+    unsafe {
+        renderer.textures[texture_page].replace_image(
+            &renderer.adapter,
+            &renderer.device,
+            &mut renderer.command_pool,
+            &mut renderer.queue_group.queues[0],
+            &*image,
+            image.width() as usize,
+            image.height() as usize,
+            gfx_hal::image::Filter::Nearest,
+        )
+    }
+}
+
 pub(super) fn recreate_swapchain(renderer: &mut RC, window: &Window) -> Result<(), Error> {
     let caps = renderer.surface.capabilities(&renderer.adapter.physical_device);
     let formats = renderer
@@ -1,33 +1,147 @@
 use super::{
-    serialization_util, Scene, SceneMode, SerializedEntity, SingletonDatabase, ENTITY_SUBPATH,
-    PREFAB_DIRECTORY, SCENE_DIRECTORY, SINGLETONS_SUBPATH,
+    serialization_util, ComponentList, EventChannel, LevelSolved, Scene, SceneMode, SceneRequested,
+    SceneSwitcher, SerializedEntity, SingletonDatabase, ENTITY_SUBPATH, PREFAB_DIRECTORY, SCENE_DIRECTORY,
+    SINGLETONS_SUBPATH,
 };
 use anyhow::Error;
-use lazy_static::lazy_static;
-use std::sync::Mutex;
-
-lazy_static! {
-    pub static ref CURRENT_SCENE: Mutex<Scene> = Mutex::new(Scene::new("NULL".to_string()));
-    pub static ref NEXT_SCENE: Mutex<Option<Scene>> = Mutex::new(Some(Scene::new("1".to_string())));
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What a Scene wants the SceneStack to do at the end of the frame.
+#[derive(Debug, Clone)]
+pub enum SceneTransition {
+    /// Unload every Scene on the stack and load this one.
+    Switch(Scene),
+    /// Load this Scene on top of the stack. If `update_below` is set, the Scene
+    /// beneath it keeps running its systems.
+    PushOverlay { scene: Scene, update_below: bool },
+    /// Unload this Scene, and any overlays on top of it.
+    Pop,
 }
 
-pub fn current_scene_mode() -> SceneMode {
-    CURRENT_SCENE.lock().unwrap().mode()
+/// The Scene an Ecs was loaded from, and what it wants to happen to the SceneStack next.
+#[derive(Debug)]
+pub struct SceneState {
+    current: Scene,
+    transition: Option<SceneTransition>,
+    scene_mode_request: Option<SceneMode>,
+    step_requested: bool,
+    /// `Some(true)` clears the stale handles it finds, too.
+    handle_validation_request: Option<bool>,
 }
 
-pub fn current_scene_name() -> String {
-    CURRENT_SCENE.lock().unwrap().name().to_owned()
-}
+impl SceneState {
+    pub fn new(current: Scene) -> Self {
+        Self {
+            current,
+            transition: None,
+            scene_mode_request: None,
+            step_requested: false,
+            handle_validation_request: None,
+        }
+    }
 
-pub fn set_next_scene(scene: Scene) -> bool {
-    if scene_exists(&scene) == false {
-        return false;
+    pub fn current(&self) -> &Scene {
+        &self.current
+    }
+
+    pub fn current_scene_mode(&self) -> SceneMode {
+        self.current.mode()
+    }
+
+    pub fn current_scene_name(&self) -> &str {
+        self.current.name()
+    }
+
+    pub fn set_next_scene(&mut self, scene: Scene) -> bool {
+        if scene_exists(&scene) == false {
+            return false;
+        }
+
+        self.transition = Some(SceneTransition::Switch(scene));
+        true
+    }
+
+    pub fn push_overlay(&mut self, scene: Scene, update_below: bool) -> bool {
+        if scene_exists(&scene) == false {
+            return false;
+        }
+
+        self.transition = Some(SceneTransition::PushOverlay { scene, update_below });
+        true
+    }
+
+    pub fn pop_overlay(&mut self) {
+        self.transition = Some(SceneTransition::Pop);
+    }
+
+    /// Clockwork takes this at the end of the frame to change the SceneStack.
+    pub fn take_transition(&mut self) -> Option<SceneTransition> {
+        self.transition.take()
     }
 
-    let mut next_scene_handle = NEXT_SCENE.lock().unwrap();
-    *next_scene_handle = Some(scene);
+    /// Asks to move into a new SceneMode at the end of the frame. Clockwork snapshots the
+    /// world when we leave Draft, and restores it when we come back.
+    pub fn request_scene_mode(&mut self, scene_mode: SceneMode) {
+        self.scene_mode_request = Some(scene_mode);
+    }
+
+    pub fn take_scene_mode_request(&mut self) -> Option<SceneMode> {
+        self.scene_mode_request.take()
+    }
+
+    /// Asks to run exactly one tick, as if we were Playing, and then stay Paused.
+    pub fn request_step(&mut self) {
+        self.scene_mode_request = Some(SceneMode::Paused);
+        self.step_requested = true;
+    }
+
+    pub fn take_step_request(&mut self) -> bool {
+        std::mem::take(&mut self.step_requested)
+    }
+
+    /// Asks for a check for stale Entity handles at the end of the frame. If `clear`
+    /// is true, they're cleared as well as reported.
+    pub fn request_handle_validation(&mut self, clear: bool) {
+        self.handle_validation_request = Some(clear);
+    }
 
-    true
+    pub fn take_handle_validation_request(&mut self) -> Option<bool> {
+        self.handle_validation_request.take()
+    }
+
+    pub fn set_scene_mode(&mut self, scene_mode: SceneMode) {
+        match scene_mode {
+            SceneMode::Draft => self.current.stop_scene(),
+            SceneMode::Playing => self.current.play_scene(),
+            SceneMode::Paused => self.current.pause_scene(),
+        }
+    }
+}
+
+/// Turns every LevelSolved into a SceneRequested, if the Flag is a SceneSwitcher.
+pub fn request_solved_level_scenes(
+    level_solved: &EventChannel<LevelSolved>,
+    scene_switchers: &ComponentList<SceneSwitcher>,
+    scene_requested: &mut EventChannel<SceneRequested>,
+) {
+    for solved in level_solved.iter() {
+        if let Some(scene_switcher) = scene_switchers.get(&solved.flag) {
+            scene_requested.send(SceneRequested {
+                scene: Scene::new(scene_switcher.inner().target_scene.clone()),
+            });
+        } else {
+            error!("Flag wasn't a scene switcher? This is chaos!");
+        }
+    }
+}
+
+pub fn handle_scene_requests(scene_requested: &EventChannel<SceneRequested>, scene_state: &mut SceneState) {
+    for request in scene_requested.iter() {
+        if scene_state.set_next_scene(request.scene.clone()) == false {
+            error!("Couldn't switch to {}! Does it exist?", request.scene);
+        }
+    }
 }
 
 pub fn create_scene(scene_name: &str) -> Result<bool, Error> {
@@ -43,16 +157,17 @@ pub fn create_scene(scene_name: &str) -> Result<bool, Error> {
 
     // Entities Data
     {
-        let blank_entity_save_data: Vec<SerializedEntity> = vec![];
+        let blank_entity_save_data: HashMap<Uuid, SerializedEntity> = HashMap::new();
         let entity_path = format!("{}/{}", scene_path, ENTITY_SUBPATH);
-        serialization_util::save_serialized_file(&blank_entity_save_data, &entity_path)?;
+        serialization_util::save_versioned_file(&blank_entity_save_data, &entity_path)?;
     }
 
-    // Make a blank singleton database!
+    // New Scenes start with the default singletons
     {
-        let singleton_database_blank: SingletonDatabase = SingletonDatabase::default();
+        let singleton_database_blank: SingletonDatabase =
+            serialization_util::singleton_components::load_default_singleton_database()?;
         let singleton_path = format!("{}/{}", scene_path, SINGLETONS_SUBPATH);
-        serialization_util::save_serialized_file(&singleton_database_blank, &singleton_path)?;
+        serialization_util::save_versioned_file(&singleton_database_blank, &singleton_path)?;
     }
 
     Ok(true)
@@ -0,0 +1,169 @@
+use super::{
+    command_buffer, grid_system::Grid, ActionMap, Ecs, ResourcesDatabase, SceneMode, UiHandler, Vec2,
+};
+use imgui::*;
+use std::time::{Duration, Instant};
+
+/// The Stages of a Tick, in the order they run. Systems inside a Stage run in the
+/// order they were added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Input,
+    Gameplay,
+    PostGameplay,
+    PreRender,
+}
+
+impl Stage {
+    /// These run inside the fixed update loop. PreRender runs once a frame.
+    pub const FIXED_UPDATE: [Stage; 3] = [Stage::Input, Stage::Gameplay, Stage::PostGameplay];
+}
+
+use bitflags::bitflags;
+bitflags! {
+    /// Which SceneModes a System runs in.
+    pub struct RunsIn: u8 {
+        const DRAFT                 =   0b0000_0001;
+        const PLAYING               =   0b0000_0010;
+        const PAUSED                =   0b0000_0100;
+    }
+}
+
+impl RunsIn {
+    pub fn scene_mode(&self, scene_mode: SceneMode) -> bool {
+        match scene_mode {
+            SceneMode::Draft => self.contains(RunsIn::DRAFT),
+            SceneMode::Playing => self.contains(RunsIn::PLAYING),
+            SceneMode::Paused => self.contains(RunsIn::PAUSED),
+        }
+    }
+}
+
+/// Everything a System is allowed to touch.
+pub struct SystemContext<'a> {
+    pub ecs: &'a mut Ecs,
+    pub grid: &'a mut Grid,
+    pub action_map: &'a ActionMap,
+    pub resources: &'a ResourcesDatabase,
+    pub delta_time: f32,
+}
+
+pub type SystemFn = fn(&mut SystemContext<'_>);
+
+pub struct ScheduledSystem {
+    name: &'static str,
+    stage: Stage,
+    runs_in: RunsIn,
+    system: SystemFn,
+    last_duration: Option<Duration>,
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    systems: Vec<ScheduledSystem>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a system to the end of its Stage.
+    pub fn add_system(&mut self, name: &'static str, stage: Stage, runs_in: RunsIn, system: SystemFn) {
+        let position = self
+            .systems
+            .iter()
+            .position(|s| s.stage > stage)
+            .unwrap_or_else(|| self.systems.len());
+
+        self.systems.insert(
+            position,
+            ScheduledSystem {
+                name,
+                stage,
+                runs_in,
+                system,
+                last_duration: None,
+            },
+        );
+    }
+
+    /// Runs every system in the Stage which runs in the given SceneMode. The end of a
+    /// Stage is a sync point, where we apply the Ecs's CommandBuffer, propagate any
+    /// dirty Transforms and rebuild the TagIndex, so the next Stage sees it all.
+    pub fn run_stage(&mut self, stage: Stage, scene_mode: SceneMode, context: &mut SystemContext<'_>) {
+        for system in self.systems.iter_mut().filter(|s| s.stage == stage) {
+            if system.runs_in.scene_mode(scene_mode) {
+                let start = Instant::now();
+                (system.system)(context);
+                system.last_duration = Some(start.elapsed());
+            } else {
+                system.last_duration = None;
+            }
+        }
+
+        command_buffer::apply_commands(context.ecs, context.grid, context.resources.prefabs());
+        context.ecs.scene_graph.propagate_transforms(
+            &mut context.ecs.component_database.transforms,
+            &context.ecs.component_database.graph_nodes,
+        );
+        context
+            .ecs
+            .tag_index
+            .rebuild(&mut context.ecs.component_database.tags);
+    }
+
+    /// Runs one tick of every fixed Stage.
+    pub fn run_fixed_update(&mut self, scene_mode: SceneMode, context: &mut SystemContext<'_>) {
+        for stage in Stage::FIXED_UPDATE.iter() {
+            self.run_stage(*stage, scene_mode, context);
+        }
+    }
+
+    /// Runs the PreRender Stage, which ends the frame. Events last for the whole frame, so
+    /// the editor and every tick can read them, and are cleared here once everyone has.
+    pub fn run_pre_render(&mut self, scene_mode: SceneMode, context: &mut SystemContext<'_>) {
+        self.run_stage(Stage::PreRender, scene_mode, context);
+        context.ecs.events.clear();
+    }
+
+    pub fn create_imgui_window(&self, ui_handler: &mut UiHandler<'_>) -> bool {
+        let mut is_opened = true;
+
+        let ui = &mut ui_handler.ui;
+        let scheduler_window = Window::new(im_str!("Scheduler"))
+            .size(Vec2::new(450.0, 300.0).into(), imgui::Condition::FirstUseEver)
+            .opened(&mut is_opened);
+
+        if let Some(window) = scheduler_window.begin(ui) {
+            ui.columns(4, im_str!("scheduler_columns"), true);
+            for header in &["Stage", "System", "Runs In", "Last Run"] {
+                ui.text(header);
+                ui.next_column();
+            }
+            ui.separator();
+
+            for system in self.systems.iter() {
+                ui.text(format!("{:?}", system.stage));
+                ui.next_column();
+
+                ui.text(system.name);
+                ui.next_column();
+
+                ui.text(format!("{:?}", system.runs_in));
+                ui.next_column();
+
+                match system.last_duration {
+                    Some(duration) => ui.text(format!("{:.3} ms", duration.as_secs_f64() * 1000.0)),
+                    None => ui.text_disabled("Skipped"),
+                }
+                ui.next_column();
+            }
+
+            ui.columns(1, im_str!("scheduler_columns_end"), false);
+            window.end(ui);
+        }
+
+        is_opened
+    }
+}
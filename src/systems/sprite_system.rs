@@ -1,6 +1,6 @@
 use super::{
-    number_util, sprite_resources::FrameData, Axis, ComponentList, ResourcesDatabase, Sprite, StandardQuad,
-    StandardTexture, TextureDescription, Vec2,
+    number_util, sprite_resources::FrameData, Affine2, ComponentList, ResourcesDatabase, Sprite,
+    StandardQuad, StandardTexture, TextureDescription, Vec2,
 };
 
 pub fn update_sprites<'a>(
@@ -18,10 +18,13 @@ pub fn update_sprites<'a>(
     }
 }
 
-pub fn to_standard_quad(sprite: &Sprite, pos: Vec2, resources: &ResourcesDatabase) -> StandardQuad {
+/// Scaling, rotating, and flipping all come from the Transform. To flip a Sprite,
+/// give its Transform a negative scale.
+pub fn to_standard_quad(sprite: &Sprite, transform: &Affine2, resources: &ResourcesDatabase) -> StandardQuad {
     // Default
     let mut standard_quad = StandardQuad {
-        pos,
+        transform: *transform,
+        pos: Vec2::ZERO,
         draw_order: sprite.running_data.draw_order,
         color: sprite.running_data.tint,
         image_size: Vec2::ZERO,
@@ -42,26 +45,8 @@ pub fn to_standard_quad(sprite: &Sprite, pos: Vec2, resources: &ResourcesDatabas
                 texture_page: sprite_data.texture_page.unwrap(),
             });
 
-            standard_quad.pos = pos
-                - sprite_data
-                    .origin
-                    .gfx_adjustment(sprite_data.size)
-                    .cwise_product(sprite.running_data.scale);
-
-            standard_quad.image_size = {
-                let mut size: Vec2 = Vec2::from(sprite_data.size).cwise_product(sprite.running_data.scale);
-                if sprite.running_data.facing_horizontal != sprite_data.facing_horizontal {
-                    standard_quad.pos.x += size.x;
-                    size.reflected(Axis::X);
-                }
-
-                if sprite.running_data.facing_vertical != sprite_data.facing_vertical {
-                    standard_quad.pos.y += size.y;
-                    size.reflected(Axis::Y);
-                }
-
-                size
-            };
+            standard_quad.pos = -sprite_data.origin.gfx_adjustment(sprite_data.size);
+            standard_quad.image_size = Vec2::from(sprite_data.size);
         } else {
             log_once::error_once!(
                 "We're attempting to get {} from the Resources Sprite Database, but it's not there."
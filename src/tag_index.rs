@@ -0,0 +1,58 @@
+use super::{ComponentList, Entity, Tags};
+use std::collections::HashMap;
+
+/// Every Entity with Tags, by tag. The Scheduler rebuilds this at every sync point,
+/// so Entities tagged during a Stage show up once it's over.
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    entities: HashMap<String, Vec<Entity>>,
+    /// Every Entity with Tags when we last rebuilt, so we can tell if any came or went.
+    tagged: Vec<Entity>,
+}
+
+impl TagIndex {
+    pub fn new(tags: &mut ComponentList<Tags>) -> Self {
+        let mut tag_index = Self::default();
+        tag_index.rebuild(tags);
+        tag_index
+    }
+
+    /// Every Entity with this tag, in Entity order.
+    pub fn entities_with(&self, tag: &str) -> &[Entity] {
+        self.entities
+            .get(tag)
+            .map(|entities| entities.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Rebuilds the index if any Tags changed, or any Entity gained or lost its Tags.
+    pub fn rebuild(&mut self, tags: &mut ComponentList<Tags>) {
+        let same_entities = tags
+            .iter()
+            .map(|tags_c| tags_c.entity_id())
+            .eq(self.tagged.iter().copied());
+
+        if same_entities && tags.iter().any(|tags_c| tags_c.inner().is_dirty()) == false {
+            return;
+        }
+
+        self.entities.clear();
+        self.tagged.clear();
+
+        for tags_c in tags.iter_mut() {
+            let entity = tags_c.entity_id();
+            self.tagged.push(entity);
+
+            for tag in tags_c.inner().tags().iter() {
+                let entities = self.entities.entry(tag.clone()).or_default();
+
+                // An Entity can list the same tag twice.
+                if entities.last() != Some(&entity) {
+                    entities.push(entity);
+                }
+            }
+
+            tags_c.inner_mut().clear_dirty();
+        }
+    }
+}
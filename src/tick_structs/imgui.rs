@@ -1,6 +1,8 @@
 use super::{
-    game_config::Config, imgui_component_utils::EntityListInformation, ClipboardSupport, Entity,
-    EntityAllocator, Window,
+    game_config::Config,
+    imgui_component_utils::EntityListInformation,
+    prefab_system::{PrefabPropagation, PrefabUsages},
+    ClipboardSupport, Entity, EntityAllocator, Window,
 };
 use anyhow::Error;
 use imgui::{Context, FontConfig, FontGlyphRanges, FontSource, Ui};
@@ -114,6 +116,9 @@ impl ImGui {
             scene_graph_entities: &mut self.meta_data.entity_vec,
             entity_list_information: &mut self.meta_data.entity_list_information,
             scene_changing_info: &mut self.meta_data.scene_changing_info,
+            prefab_propagation: &mut self.meta_data.prefab_propagation,
+            prefab_usages: &mut self.meta_data.prefab_usages,
+            find_query: &mut self.meta_data.find_query,
             request_save: pressed_save && (ui.io().key_ctrl || ui.io().key_super),
             ui,
         })
@@ -195,12 +200,15 @@ pub struct UiHandler<'a> {
     pub scene_graph_entities: &'a mut Vec<Entity>,
     pub entity_list_information: &'a mut HashMap<String, EntityListInformation>,
     pub scene_changing_info: &'a mut SceneImGuiManager,
+    pub prefab_propagation: &'a mut Option<PrefabPropagation>,
+    pub prefab_usages: &'a mut Option<PrefabUsages>,
+    pub find_query: &'a mut String,
     request_save: bool,
 }
 
 impl<'a> UiHandler<'a> {
-    pub fn can_save_scene(&self) -> bool {
-        if super::scene_system::current_scene_mode() == super::SceneMode::Draft {
+    pub fn can_save_scene(&self, scene_mode: super::SceneMode) -> bool {
+        if scene_mode == super::SceneMode::Draft {
             self.request_save
         } else {
             false
@@ -222,6 +230,12 @@ pub struct ImGuiMetaData {
     pub entity_list_information: HashMap<String, EntityListInformation>,
     #[serde(skip)]
     pub scene_changing_info: SceneImGuiManager,
+    #[serde(skip)]
+    pub prefab_propagation: Option<PrefabPropagation>,
+    #[serde(skip)]
+    pub prefab_usages: Option<PrefabUsages>,
+    #[serde(skip)]
+    pub find_query: String,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -229,6 +243,8 @@ pub struct SceneImGuiManager {
     pub create_scene: String,
     pub switch_scene_name: String,
     pub delete_scene_name: String,
+    #[serde(default)]
+    pub push_overlay_name: String,
 }
 
 use bitflags::bitflags;
@@ -244,5 +260,6 @@ bitflags! {
         const PREFAB_INSPECTOR      =   0b0000_0000_0100_0000;
         const MAIN_MENU_BAR         =   0b0000_0000_1000_0000;
         const IMGUI_EXAMPLE         =   0b0000_0001_0000_0000;
+        const SCHEDULER             =   0b0000_0010_0000_0000;
     }
 }
@@ -1,16 +1,20 @@
 use super::imgui_system;
 
+mod affine;
 mod axis;
 mod cached_bool;
 pub mod cardinals;
 mod color;
+mod file_watcher;
 pub mod math;
 mod rect;
 mod vec;
 
 pub mod number_util;
+pub use affine::Affine2;
 pub use axis::Axis;
 pub use cached_bool::CachedBool;
 pub use color::Color;
+pub use file_watcher::FileWatcher;
 pub use rect::Rect;
 pub use vec::{Vec2, Vec2Int};
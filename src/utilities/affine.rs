@@ -0,0 +1,57 @@
+use super::Vec2;
+
+/// A 2D affine transform. The linear part is stored as where the x and y axes end up,
+/// and then we translate. Transforms compose these down the Scene Graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine2 {
+    pub x_axis: Vec2,
+    pub y_axis: Vec2,
+    pub translation: Vec2,
+}
+
+impl Affine2 {
+    pub const IDENTITY: Affine2 = Affine2 {
+        x_axis: Vec2::RIGHT,
+        y_axis: Vec2::UP,
+        translation: Vec2::ZERO,
+    };
+
+    /// Scales, then rotates counter-clockwise (in radians), then translates.
+    pub fn from_scale_rotation_translation(scale: Vec2, rotation: f32, translation: Vec2) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+
+        Self {
+            x_axis: Vec2::new(cos, sin) * scale.x,
+            y_axis: Vec2::new(-sin, cos) * scale.y,
+            translation,
+        }
+    }
+
+    /// Transforms a direction, so the translation is ignored.
+    pub fn transform_vector(&self, vector: Vec2) -> Vec2 {
+        self.x_axis * vector.x + self.y_axis * vector.y
+    }
+
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.transform_vector(point) + self.translation
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// `parent * child` is the child's transform in the parent's space.
+impl std::ops::Mul<Affine2> for Affine2 {
+    type Output = Affine2;
+
+    fn mul(self, rhs: Affine2) -> Affine2 {
+        Affine2 {
+            x_axis: self.transform_vector(rhs.x_axis),
+            y_axis: self.transform_vector(rhs.y_axis),
+            translation: self.transform_point(rhs.translation),
+        }
+    }
+}
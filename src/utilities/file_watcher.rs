@@ -0,0 +1,51 @@
+use anyhow::Result;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Watches a directory, and everything in it, for files which change on disk.
+pub struct FileWatcher {
+    // We never touch this, but it stops watching when it's dropped.
+    _watcher: RecommendedWatcher,
+    events: Receiver<DebouncedEvent>,
+    root: PathBuf,
+}
+
+impl FileWatcher {
+    /// Editors often save a file in a few steps, so we wait until it's been quiet for `delay`.
+    pub fn new(directory: &str, delay: Duration) -> Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher = notify::watcher(sender, delay)?;
+        watcher.watch(directory, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            root: std::env::current_dir()?,
+        })
+    }
+
+    /// Every file which was written, made or moved into place since we last asked, once each,
+    /// relative to where we're running from. Deleted files aren't listed.
+    pub fn changed_files(&self) -> Vec<PathBuf> {
+        let mut changed_files = vec![];
+
+        for event in self.events.try_iter() {
+            match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Rename(_, path) => {
+                    let path = path.strip_prefix(&self.root).map(PathBuf::from).unwrap_or(path);
+                    if changed_files.contains(&path) == false {
+                        changed_files.push(path);
+                    }
+                }
+                DebouncedEvent::Error(e, path) => error!("Error watching {:?} for changes: {}", path, e),
+                _ => {}
+            }
+        }
+
+        changed_files
+    }
+}
@@ -1,4 +1,4 @@
-use super::Vec2;
+use super::{Affine2, Vec2};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
@@ -44,6 +44,24 @@ impl Rect {
         ret
     }
 
+    /// The smallest Rect which holds this one after it's been through `transform`.
+    pub fn transformed(&self, transform: &Affine2) -> Self {
+        let corners = [
+            transform.transform_point(self.min),
+            transform.transform_point(Vec2::new(self.max.x, self.min.y)),
+            transform.transform_point(Vec2::new(self.min.x, self.max.y)),
+            transform.transform_point(self.max),
+        ];
+
+        let mut rect = Rect::new(corners[0], corners[0]);
+        for corner in &corners[1..] {
+            rect.min = Vec2::new(rect.min.x.min(corner.x), rect.min.y.min(corner.y));
+            rect.max = Vec2::new(rect.max.x.max(corner.x), rect.max.y.max(corner.y));
+        }
+
+        rect
+    }
+
     pub fn size(&self) -> Vec2 {
         self.max.cwise_subtraction(self.min)
     }
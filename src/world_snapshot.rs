@@ -0,0 +1,57 @@
+use super::{
+    systems::grid_system::Grid, CommandBuffer, ComponentDatabase, Ecs, Entity, EntityAllocator, Events,
+    SceneGraph, SingletonDatabase, TagIndex,
+};
+use std::collections::HashSet;
+
+/// A copy of an entire world, taken in memory. We take one when we start Playing,
+/// and restore it when we stop, so nothing done while Playing sticks around.
+pub struct WorldSnapshot {
+    component_database: ComponentDatabase,
+    singleton_database: SingletonDatabase,
+    entities: Vec<Entity>,
+    entity_allocator: EntityAllocator,
+    scene_graph: SceneGraph,
+    grid: Grid,
+    selection: HashSet<Entity>,
+}
+
+impl WorldSnapshot {
+    pub fn take(ecs: &Ecs, grid: &Grid, selection: &HashSet<Entity>) -> Self {
+        Self {
+            component_database: ecs.component_database.snapshot(),
+            singleton_database: ecs.singleton_database.snapshot(),
+            entities: ecs.entities.clone(),
+            entity_allocator: ecs.entity_allocator.clone(),
+            scene_graph: ecs.scene_graph.clone(),
+            grid: grid.clone(),
+            selection: selection.clone(),
+        }
+    }
+
+    /// Puts the world back the way it was, with the same Entities, and hands back the
+    /// editor's selection from when the snapshot was taken.
+    pub fn restore(self, ecs: &mut Ecs, grid: &mut Grid) -> HashSet<Entity> {
+        ecs.component_database = self.component_database;
+        ecs.singleton_database.restore(self.singleton_database);
+        ecs.entities = self.entities;
+        ecs.entity_allocator = self.entity_allocator;
+        ecs.scene_graph = self.scene_graph;
+        *grid = self.grid;
+
+        // Anything left over from Playing is about Entities which might not exist anymore.
+        ecs.commands = CommandBuffer::default();
+        ecs.events = Events::default();
+
+        // Our Transforms still point at the GraphNodes we snapshotted from.
+        ecs.scene_graph.relink(
+            &ecs.entities,
+            &mut ecs.component_database.transforms,
+            &mut ecs.component_database.graph_nodes,
+        );
+        // The snapshot's Tags look clean, but they're not what the index was built from.
+        ecs.tag_index = TagIndex::new(&mut ecs.component_database.tags);
+
+        self.selection
+    }
+}